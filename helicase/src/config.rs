@@ -31,6 +31,50 @@ pub mod advanced {
     pub const RETURN_DNA_CHUNK: Config = 1 << 8;
     pub const MERGE_DNA_CHUNKS: Config = 1 << 9;
     pub const MERGE_RECORDS: Config = 1 << 10;
+    pub const COMPUTE_GAP_MASK: Config = 1 << 11;
+    pub const RAW_SEQUENCE: Config = 1 << 12;
+    pub const COMPUTE_LINE_WIDTH: Config = 1 << 13;
+    pub const FASTA_COMMENTS: Config = 1 << 14;
+    pub const VALIDATE: Config = 1 << 15;
+    pub const COMPUTE_BASE_COUNTS: Config = 1 << 16;
+    pub const SKIP_EMPTY_RECORDS: Config = 1 << 17;
+    pub const ALPHABET_PROTEIN: Config = 1 << 18;
+    pub const TOLERATE_BLANK_LINES: Config = 1 << 19;
+    pub const ALPHABET_RNA: Config = 1 << 20;
+    pub const COMPUTE_SEQ_HASH: Config = 1 << 21;
+
+    /// Bits 48..54 store the k-mer length for
+    /// [`Event::Kmer`](crate::parser::Event) emission; `0` disables it.
+    pub const KMER_SHIFT: u32 = 48;
+    pub const KMER_MASK: Config = 0b11_1111 << KMER_SHIFT;
+
+    /// The k-mer length emitted under this configuration, or `0` when
+    /// disabled.
+    #[inline(always)]
+    pub const fn kmer_k(config: Config) -> usize {
+        ((config & KMER_MASK) >> KMER_SHIFT) as usize
+    }
+
+    /// Bits 40..48 store the Phred quality offset; `0` means the default 33.
+    pub const PHRED_SHIFT: u32 = 40;
+
+    /// The Phred offset used to decode quality bytes under this configuration.
+    #[inline(always)]
+    pub const fn phred_offset(config: Config) -> u8 {
+        let byte = ((config >> PHRED_SHIFT) & 0xFF) as u8;
+        if byte == 0 { 33 } else { byte }
+    }
+
+    /// Bits 56..64 store the FASTA record separator byte; `0` means the
+    /// default `>`.
+    pub const HEADER_BYTE_SHIFT: u32 = 56;
+
+    /// The byte starting a FASTA header line under this configuration.
+    #[inline(always)]
+    pub const fn header_byte(config: Config) -> u8 {
+        let byte = (config >> HEADER_BYTE_SHIFT) as u8;
+        if byte == 0 { b'>' } else { byte }
+    }
     // pub const RETURN_START_HEADER: Config = 1 << 6;
     // pub const RETURN_END_HEADER: Config = 1 << 7;
     // pub const RETURN_START_DNA_CHUNK: Config = 1 << 8;
@@ -46,6 +90,20 @@ use advanced::*;
 #[derive(Clone, Copy)]
 pub struct ParserOptions(Config);
 
+/// Which events the parser iterator yields; see [`ParserOptions::events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Events {
+    /// One [`Record`](crate::Event::Record) event per record, with the DNA
+    /// chunks of a record merged into its accessors.
+    PerRecord,
+    /// One [`DnaChunk`](crate::Event::DnaChunk) event per DNA chunk, without
+    /// record events.
+    PerChunk,
+    /// No events at all: records and chunks are merged, and the accessors
+    /// hold the whole input once the iterator ends.
+    Merged,
+}
+
 impl ParserOptions {
     /// Creates a default configuration, which computes headers and DNA as bytes.
     #[inline(always)]
@@ -59,9 +117,44 @@ impl ParserOptions {
         Self(config)
     }
 
+    /// A QC preset: per-record sequence length, base counts and quality,
+    /// without headers or sequence bytes.
+    #[inline(always)]
+    pub const fn qc() -> Self {
+        Self::default()
+            .ignore_headers()
+            .dna_len_only()
+            .compute_base_counts()
+            .compute_quality()
+    }
+
+    /// An indexing preset: headers and sequence lengths only, e.g. to build
+    /// a `.fai`-style lookup table.
+    #[inline(always)]
+    pub const fn index() -> Self {
+        Self::default().dna_len_only()
+    }
+
+    /// OR another set of flags into this one, e.g. to extend a preset:
+    /// `ParserOptions::index().merge(ParserOptions::qc().config())`.
+    /// Contradictory combinations are still rejected by
+    /// [`config`](#method.config).
+    #[inline(always)]
+    pub const fn merge(self, other: Config) -> Self {
+        Self(self.0 | other)
+    }
+
     /// Build the configuration of the parser.
+    /// This rejects contradictory combinations, currently
+    /// [`raw_sequence`](#method.raw_sequence) with
+    /// [`skip_non_actg`](#method.skip_non_actg); the panic happens at compile
+    /// time when the result feeds a `const` config.
     #[inline(always)]
     pub const fn config(self) -> Config {
+        assert!(
+            flag_is_not_set(self.0, RAW_SEQUENCE) || flag_is_not_set(self.0, SPLIT_NON_ACTG),
+            "raw_sequence is incompatible with skip_non_actg"
+        );
         self.0
     }
 
@@ -89,6 +182,215 @@ impl ParserOptions {
         Self(self.0 & !COMPUTE_QUALITY)
     }
 
+    /// Enable the computation of a gap mask marking `-`/`.` positions.
+    /// This composes with [`dna_string`](#method.dna_string), which keeps the gaps in the byte output.
+    #[inline(always)]
+    pub const fn compute_gap_mask(self) -> Self {
+        Self(self.0 | COMPUTE_GAP_MASK)
+    }
+
+    /// Disable the computation of the gap mask (default).
+    #[inline(always)]
+    pub const fn ignore_gap_mask(self) -> Self {
+        Self(self.0 & !COMPUTE_GAP_MASK)
+    }
+
+    /// Enable the accumulation of per-record A/C/T/G counts,
+    /// reported by [`get_base_counts`](crate::parser::Parser::get_base_counts).
+    /// Without [`split_non_actg`](#method.split_non_actg), non-ACTG bases are
+    /// counted under the base sharing their 2-bit code (e.g. `N` counts as `G`).
+    #[inline(always)]
+    pub const fn compute_base_counts(self) -> Self {
+        Self(self.0 | COMPUTE_BASE_COUNTS)
+    }
+
+    /// Disable the accumulation of base counts (default).
+    #[inline(always)]
+    pub const fn ignore_base_counts(self) -> Self {
+        Self(self.0 & !COMPUTE_BASE_COUNTS)
+    }
+
+    /// Enable the accumulation of a per-record sequence hash,
+    /// reported by [`get_seq_hash`](crate::parser::Parser::get_seq_hash).
+    /// The hash is a fast FxHash-style fold meant for exact-duplicate
+    /// detection, not a cryptographic digest.
+    #[inline(always)]
+    pub const fn compute_seq_hash(self) -> Self {
+        Self(self.0 | COMPUTE_SEQ_HASH)
+    }
+
+    /// Disable the accumulation of the sequence hash (default).
+    #[inline(always)]
+    pub const fn ignore_seq_hash(self) -> Self {
+        Self(self.0 & !COMPUTE_SEQ_HASH)
+    }
+
+    /// Accumulate sequences across record boundaries instead of clearing at
+    /// each new record, e.g. to treat a multi-contig FASTA as one concatenated
+    /// sequence. The final `get_dna_*` then holds the whole file's bases.
+    /// Headers are not merged: `get_header` returns the most recent one.
+    /// This implies [`MERGE_DNA_CHUNKS`].
+    #[inline(always)]
+    pub const fn merge_records(self) -> Self {
+        Self(self.0 | MERGE_RECORDS | MERGE_DNA_CHUNKS)
+    }
+
+    /// Clear the accumulated record state at each new record (default).
+    #[inline(always)]
+    pub const fn separate_records(self) -> Self {
+        Self(self.0 & !MERGE_RECORDS)
+    }
+
+    /// Suppress the [`Record`](crate::parser::Event) event of FASTA records
+    /// with no sequence, e.g. a header line directly followed by another.
+    #[inline(always)]
+    pub const fn skip_empty_records(self) -> Self {
+        Self(self.0 | SKIP_EMPTY_RECORDS)
+    }
+
+    /// Emit a [`Record`](crate::parser::Event) event with an empty sequence
+    /// for FASTA headers with no following sequence (default).
+    #[inline(always)]
+    pub const fn keep_empty_records(self) -> Self {
+        Self(self.0 & !SKIP_EMPTY_RECORDS)
+    }
+
+    /// Skip blank lines between FASTQ records instead of counting them
+    /// toward the 4-line cycle, which stray blank lines in hand-edited files
+    /// would otherwise desynchronize.
+    /// Only lines at a record boundary are skipped, so legitimately empty
+    /// sequence or quality lines inside a record are still counted.
+    #[inline(always)]
+    pub const fn tolerate_blank_lines(self) -> Self {
+        Self(self.0 | TOLERATE_BLANK_LINES)
+    }
+
+    /// Count every line toward the FASTQ 4-line cycle (default).
+    #[inline(always)]
+    pub const fn reject_blank_lines(self) -> Self {
+        Self(self.0 & !TOLERATE_BLANK_LINES)
+    }
+
+    /// Use the protein alphabet for the sequence membership test: the 26
+    /// letters of either case plus the `*` stop codon, instead of ACTG.
+    /// With [`split_non_actg`](#method.split_non_actg) or
+    /// [`skip_non_actg`](#method.skip_non_actg), sequences then only split at
+    /// bytes outside that alphabet, so `X` or `*` no longer breaks a chunk.
+    /// The 2-bit formats ([`dna_packed`](#method.dna_packed) and
+    /// [`dna_columnar`](#method.dna_columnar)) remain DNA-only.
+    #[inline(always)]
+    pub const fn alphabet_protein(self) -> Self {
+        Self((self.0 & !ALPHABET_RNA) | ALPHABET_PROTEIN)
+    }
+
+    /// Use the RNA alphabet for the sequence membership test: ACG plus `U`
+    /// of either case, which shares T's 2-bit code, so RNA sequences are not
+    /// fragmented by [`split_non_actg`](#method.split_non_actg).
+    #[inline(always)]
+    pub const fn alphabet_rna(self) -> Self {
+        Self((self.0 & !ALPHABET_PROTEIN) | ALPHABET_RNA)
+    }
+
+    /// Use the ACTG alphabet for the sequence membership test (default).
+    #[inline(always)]
+    pub const fn alphabet_dna(self) -> Self {
+        Self(self.0 & !(ALPHABET_PROTEIN | ALPHABET_RNA))
+    }
+
+    /// Emit an [`Event::Kmer`](crate::parser::Event) for every k-mer
+    /// (`k <= 32`) of the sequence, as a 2-bit packed `u64` with the first
+    /// base in the low bits, matching
+    /// [`PackedDNA::kmers`](crate::dna_format::PackedDNA::kmers), without
+    /// materializing the record.
+    /// This implies [`SPLIT_NON_ACTG`] so that the 2-bit codes are valid;
+    /// the rolling window resets at non-ACTG bases and at record starts, so
+    /// k-mers never span them.
+    #[inline(always)]
+    pub const fn emit_kmers(self, k: u8) -> Self {
+        assert!(k != 0 && k <= 32);
+        Self((self.0 & !KMER_MASK) | ((k as Config) << KMER_SHIFT) | SPLIT_NON_ACTG)
+    }
+
+    /// Disable the k-mer emission (default).
+    #[inline(always)]
+    pub const fn ignore_kmers(self) -> Self {
+        Self(self.0 & !KMER_MASK)
+    }
+
+    /// Start FASTA records at `byte` instead of the default `>`, for
+    /// `>`-less internal formats (e.g. `#`-delimited variants).
+    #[inline(always)]
+    pub const fn fasta_header_byte(self, byte: u8) -> Self {
+        Self((self.0 & !(0xFF << HEADER_BYTE_SHIFT)) | ((byte as Config) << HEADER_BYTE_SHIFT))
+    }
+
+    /// Set the Phred offset used to decode quality bytes (default 33;
+    /// 64 for legacy Illumina 1.3-1.7 files).
+    /// The offset-less quality accessors such as
+    /// [`get_quality_scores`](crate::FastqParser::get_quality_scores) decode
+    /// with it, so the encoding is chosen once at construction.
+    #[inline(always)]
+    pub const fn phred_offset(self, offset: u8) -> Self {
+        Self((self.0 & !(0xFF << PHRED_SHIFT)) | ((offset as Config) << PHRED_SHIFT))
+    }
+
+    /// Skip `;`-prefixed comment lines of the classic FASTA/Pearson format,
+    /// which would otherwise be parsed as sequence.
+    #[inline(always)]
+    pub const fn skip_fasta_comments(self) -> Self {
+        Self(self.0 | FASTA_COMMENTS)
+    }
+
+    /// Parse `;`-prefixed lines as sequence (default).
+    #[inline(always)]
+    pub const fn keep_fasta_comments(self) -> Self {
+        Self(self.0 & !FASTA_COMMENTS)
+    }
+
+    /// Enable tracking of the sequence line width of FASTA records,
+    /// reported by [`line_width`](crate::parser::Parser::line_width).
+    #[inline(always)]
+    pub const fn compute_line_width(self) -> Self {
+        Self(self.0 | COMPUTE_LINE_WIDTH)
+    }
+
+    /// Check the 4-line FASTQ invariants while parsing, for use with
+    /// [`ValidatedFastqParser`](crate::parser::ValidatedFastqParser).
+    #[inline(always)]
+    pub const fn validate(self) -> Self {
+        Self(self.0 | VALIDATE)
+    }
+
+    /// Assume well-formed input (default), keeping the unchecked fast path.
+    #[inline(always)]
+    pub const fn skip_validation(self) -> Self {
+        Self(self.0 & !VALIDATE)
+    }
+
+    /// Disable the tracking of the sequence line width (default).
+    #[inline(always)]
+    pub const fn ignore_line_width(self) -> Self {
+        Self(self.0 & !COMPUTE_LINE_WIDTH)
+    }
+
+    /// Return multi-line FASTA sequences as a zero-copy slice of the input,
+    /// including the embedded newlines, instead of buffering them.
+    /// This only affects [`dna_string`](#method.dna_string) output on random-access inputs.
+    /// It is incompatible with [`skip_non_actg`](#method.skip_non_actg), which
+    /// cuts sequences into spans, and pointless for the packed and columnar
+    /// outputs, which re-encode the bases anyway; the combination is rejected
+    /// when the config is finalized.
+    #[inline(always)]
+    pub const fn raw_sequence(self) -> Self {
+        Self(self.0 | RAW_SEQUENCE)
+    }
+
+    /// Buffer multi-line FASTA sequences so that newlines are stripped (default).
+    #[inline(always)]
+    pub const fn buffered_sequence(self) -> Self {
+        Self(self.0 & !RAW_SEQUENCE)
+    }
+
     /// Disable the computation of DNA.
     #[inline(always)]
     pub const fn ignore_dna(self) -> Self {
@@ -102,6 +404,25 @@ impl ParserOptions {
         )
     }
 
+    /// Only compute sequence lengths: set [`COMPUTE_DNA_LEN`] and clear the
+    /// DNA formats, so that a length-only pass neither buffers sequence bytes
+    /// nor computes the columnar/packed forms.
+    /// Non-ACTG bases always count toward the length; combine with
+    /// [`skip_non_actg`](#method.skip_non_actg) to also accumulate the
+    /// ACTG-only count behind `get_actg_len`.
+    #[inline(always)]
+    pub const fn dna_len_only(self) -> Self {
+        Self(
+            (self.0
+                & !(COMPUTE_DNA_STRING
+                    | COMPUTE_DNA_COLUMNAR
+                    | COMPUTE_DNA_PACKED
+                    | SPLIT_NON_ACTG
+                    | RETURN_DNA_CHUNK))
+                | COMPUTE_DNA_LEN,
+        )
+    }
+
     /// Set the DNA format to bytes (default).
     #[inline(always)]
     pub const fn dna_string(self) -> Self {
@@ -149,6 +470,10 @@ impl ParserOptions {
     }
 
     /// Skip non-ACTG bases in the sequence, chunks of ACTG bases get merged together.
+    /// The accumulation survives the end of the input: after the iterator
+    /// returns `None`, the `get_dna_*` accessors still reflect the final
+    /// record, even without [`RETURN_RECORD`], so no explicit flush call is
+    /// needed for a file ending mid-sequence.
     #[inline(always)]
     pub const fn skip_non_actg(self) -> Self {
         Self((self.0 & !RETURN_DNA_CHUNK) | SPLIT_NON_ACTG | MERGE_DNA_CHUNKS)
@@ -175,4 +500,46 @@ impl ParserOptions {
             Self(self.0 & !RETURN_DNA_CHUNK)
         }
     }
+
+    /// Set which events the iterator yields, replacing the raw
+    /// [`RETURN_RECORD`] / [`RETURN_DNA_CHUNK`] / [`MERGE_DNA_CHUNKS`] /
+    /// [`MERGE_RECORDS`] bit-twiddling with one cardinality choice.
+    #[inline(always)]
+    pub const fn events(self, events: Events) -> Self {
+        let cleared =
+            self.0 & !(RETURN_RECORD | RETURN_DNA_CHUNK | MERGE_DNA_CHUNKS | MERGE_RECORDS);
+        match events {
+            Events::PerRecord => Self(cleared | RETURN_RECORD | MERGE_DNA_CHUNKS),
+            Events::PerChunk => Self(cleared | RETURN_DNA_CHUNK),
+            Events::Merged => Self(cleared | MERGE_DNA_CHUNKS | MERGE_RECORDS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{advanced::*, *};
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(
+            ParserOptions::qc().config(),
+            COMPUTE_DNA_LEN | COMPUTE_BASE_COUNTS | COMPUTE_QUALITY | RETURN_RECORD
+        );
+        assert_eq!(
+            ParserOptions::index().config(),
+            COMPUTE_HEADER | COMPUTE_DNA_LEN | RETURN_RECORD
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        const MERGED: Config = ParserOptions::index()
+            .merge(ParserOptions::qc().config())
+            .config();
+        assert_eq!(
+            MERGED,
+            ParserOptions::index().config() | ParserOptions::qc().config()
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use std::fmt;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(crate) enum Nucleotide {
@@ -39,6 +41,14 @@ impl Nucleotide {
             Self::G => 'G',
         }
     }
+
+    #[inline(always)]
+    pub const fn as_rna_char(self) -> char {
+        match self {
+            Self::T => 'U',
+            _ => self.as_char(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -103,21 +113,45 @@ impl ColumnarDNA {
         self.space = 64;
     }
 
+    /// Shrink both lane stores to the words in use.
+    #[inline(always)]
+    pub fn shrink_to_fit(&mut self) {
+        self.store0.shrink_to_fit();
+        self.store1.shrink_to_fit();
+    }
+
+    /// Heap memory used by the lane stores, in bytes.
+    #[inline(always)]
+    pub fn memory_bytes(&self) -> usize {
+        (self.store0.capacity() + self.store1.capacity()) * size_of::<u64>()
+    }
+
+    /// Append a single ASCII nucleotide (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    #[inline(always)]
+    pub fn push_byte(&mut self, ch: u8) {
+        let (b0, b1) = match ch {
+            b'A' | b'a' => (0, 0),
+            b'C' | b'c' => (0, 1),
+            b'G' | b'g' => (1, 1),
+            b'T' | b't' => (1, 0),
+            _ => panic!("Invalid nucleotide: {}", ch as char),
+        };
+        self.append(b0, b1, 1);
+    }
+
     pub fn push_str(&mut self, s: &str) {
         for ch in s.bytes() {
-            let (b0, b1) = match ch {
-                b'A' | b'a' => (0, 0),
-                b'C' | b'c' => (0, 1),
-                b'G' | b'g' => (1, 1),
-                b'T' | b't' => (1, 0),
-                _ => panic!("Invalid nucleotide: {}", ch as char),
-            };
-            self.append(b0, b1, 1);
+            self.push_byte(ch);
         }
     }
 
+    /// Append the low `size` bits of the two lane words.
+    /// A single call can only carry one word per lane, so `size` must be at
+    /// most 64; this is checked with a `debug_assert`.
     #[inline(always)]
     pub fn append(&mut self, b0: u64, b1: u64, size: usize) {
+        debug_assert!(size <= 64, "append takes at most 64 bits per call");
         if size == 0 {
             // should not happen?
             return;
@@ -125,8 +159,9 @@ impl ColumnarDNA {
         if self.space == 0 {
             self.store0.push(self.b0);
             self.store1.push(self.b1);
-            self.b0 = b0;
-            self.b1 = b1;
+            // mask the bits beyond `size`, which callers leave as garbage
+            self.b0 = b0 & (!0 >> (64 - size));
+            self.b1 = b1 & (!0 >> (64 - size));
             self.space = 64 - size;
         } else {
             let low_bits: usize = size.min(self.space);
@@ -144,6 +179,125 @@ impl ColumnarDNA {
                 self.space = 64 - high_bits;
             }
         }
+        debug_assert!(self.check_invariants());
+    }
+
+    /// Shorten the logical sequence to `len` bases, keeping the allocations.
+    /// Lengths at or beyond the current one are a no-op.
+    /// The lane bits above the new length are zeroed so that later
+    /// [`append`](#method.append)s do not OR into stale bits.
+    #[inline(always)]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        let words = len / 64;
+        let rem = len % 64;
+        if words < self.store0.len() {
+            // pull the word holding the cut back into the partial registers
+            self.b0 = self.store0[words];
+            self.b1 = self.store1[words];
+            self.store0.truncate(words);
+            self.store1.truncate(words);
+        }
+        if rem == 0 {
+            self.b0 = 0;
+            self.b1 = 0;
+            self.space = 64;
+        } else {
+            let mask = !0 >> (64 - rem);
+            self.b0 &= mask;
+            self.b1 &= mask;
+            self.space = 64 - rem;
+        }
+        debug_assert!(self.check_invariants());
+    }
+
+    /// Check the internal store invariants: both lane stores have the same
+    /// length, and only the low `64 - space` bits of the partial words are set.
+    /// [`append`](Self::append) checks this with a `debug_assert`.
+    #[inline(always)]
+    pub fn check_invariants(&self) -> bool {
+        if self.store0.len() != self.store1.len() || self.space > 64 {
+            return false;
+        }
+        if self.space == 0 {
+            return true;
+        }
+        let unused = if self.space == 64 {
+            !0
+        } else {
+            !0 << (64 - self.space)
+        };
+        (self.b0 & unused) == 0 && (self.b1 & unused) == 0
+    }
+
+    /// Expose the raw bit lanes for external bit-parallel algorithms:
+    /// `(store0, store1, b0, b1, len)`, where the stores hold 64 bases per
+    /// word and `b0`/`b1` are the in-progress partial words covering the
+    /// remaining `len % 64` bases (when `len` is not a multiple of 64).
+    /// Base `i` lives at bit `i % 64` of word `i / 64`; lane 0 holds the
+    /// high bit of the 2-bit code and lane 1 the low bit, so `A` is `(0,0)`,
+    /// `C` is `(0,1)`, `T` is `(1,0)` and `G` is `(1,1)`.
+    #[inline(always)]
+    pub fn as_lanes(&self) -> (&[u64], &[u64], u64, u64, usize) {
+        (&self.store0, &self.store1, self.b0, self.b1, self.len())
+    }
+
+    /// A bitmask with one bit per base marking the positions holding the
+    /// base with 2-bit code `code` (`A`=0, `C`=1, `T`=2, `G`=3, as in
+    /// [`get_base_counts`](crate::parser::Parser::get_base_counts)).
+    /// Lane 0 holds the high bit of the code and lane 1 the low bit, so each
+    /// output word costs one logical operation per lane, not one per base.
+    pub fn base_mask(&self, code: u8) -> Vec<u64> {
+        assert!(code < 4);
+        let has_partial = self.space < 64;
+        let mut mask = Vec::with_capacity(self.store0.len() + has_partial as usize);
+        let lanes = self
+            .store0
+            .iter()
+            .zip(&self.store1)
+            .map(|(&b0, &b1)| (b0, b1))
+            .chain(has_partial.then_some((self.b0, self.b1)));
+        for (b0, b1) in lanes {
+            let m0 = if code & 0b10 != 0 { b0 } else { !b0 };
+            let m1 = if code & 0b01 != 0 { b1 } else { !b1 };
+            mask.push(m0 & m1);
+        }
+        if has_partial && self.space > 0 {
+            // the complement lanes set the garbage bits above the length
+            *mask.last_mut().unwrap() &= !0 >> self.space;
+        }
+        mask
+    }
+
+    /// The positions holding the base with 2-bit code `code`, in increasing
+    /// order, decoded from [`base_mask`](Self::base_mask) one word at a time,
+    /// e.g. all `C`/`G` positions for CpG analysis.
+    pub fn base_positions(&self, code: u8) -> impl Iterator<Item = usize> {
+        self.base_mask(code)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(word, mut bits)| {
+                core::iter::from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some(64 * word + bit)
+                })
+            })
+    }
+
+    /// Render the sequence as RNA, emitting `U` where [`Display`](fmt::Display) emits `T`.
+    /// This is only a display substitution, the columnar encoding is unchanged.
+    pub fn to_rna_string(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        for i in 0..self.len() {
+            s.push(self.get(i).unwrap().as_rna_char());
+        }
+        s
     }
 
     #[inline(always)]
@@ -165,6 +319,88 @@ impl ColumnarDNA {
     }
 }
 
+/// Compare the decoded bases to an expected ASCII sequence,
+/// case-insensitively; a length mismatch compares as unequal.
+impl PartialEq<[u8]> for ColumnarDNA {
+    fn eq(&self, other: &[u8]) -> bool {
+        const UPPERCASE_MASK: u8 = 0b1101_1111;
+        self.len() == other.len()
+            && other
+                .iter()
+                .enumerate()
+                .all(|(i, &ch)| self.get(i).unwrap().as_char() as u8 == ch & UPPERCASE_MASK)
+    }
+}
+
+impl PartialEq<&[u8]> for ColumnarDNA {
+    fn eq(&self, other: &&[u8]) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<&str> for ColumnarDNA {
+    fn eq(&self, other: &&str) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl From<&[u8]> for ColumnarDNA {
+    /// Convert from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(seq: &[u8]) -> Self {
+        let mut res = Self::with_capacity(seq.len());
+        res.extend(seq.iter().copied());
+        res
+    }
+}
+
+#[cfg(feature = "needletail")]
+impl From<&needletail::parser::SequenceRecord<'_>> for ColumnarDNA {
+    /// Convert the sequence of a needletail record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &needletail::parser::SequenceRecord<'_>) -> Self {
+        Self::from(record.seq().as_ref())
+    }
+}
+
+#[cfg(feature = "bio")]
+impl From<&bio::io::fasta::Record> for ColumnarDNA {
+    /// Convert the sequence of a rust-bio FASTA record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &bio::io::fasta::Record) -> Self {
+        Self::from(record.seq())
+    }
+}
+
+#[cfg(feature = "bio")]
+impl From<&bio::io::fastq::Record> for ColumnarDNA {
+    /// Convert the sequence of a rust-bio FASTQ record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &bio::io::fastq::Record) -> Self {
+        Self::from(record.seq())
+    }
+}
+
+impl Extend<u8> for ColumnarDNA {
+    /// Extend from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        for ch in iter {
+            self.push_byte(ch);
+        }
+    }
+}
+
+impl FromIterator<u8> for ColumnarDNA {
+    /// Collect from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
 impl fmt::Display for ColumnarDNA {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for i in 0..self.len() {
@@ -178,6 +414,89 @@ impl fmt::Display for ColumnarDNA {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_append_masks_garbage() {
+        let mut dna = ColumnarDNA::new();
+        // the lane bits above `size` are ignored
+        dna.append(!0, !0, 1);
+        dna.append(!0 << 1, !0 << 1, 1);
+        assert_eq!(dna, "GA");
+    }
+
+    #[test]
+    fn test_truncate() {
+        let seq = "ACGT".repeat(25);
+        let mut dna = ColumnarDNA::from(seq.as_bytes());
+        // a non-word-aligned cut leaves partial lane words
+        dna.truncate(70);
+        assert_eq!(dna.len(), 70);
+        assert!(dna.check_invariants());
+        assert_eq!(dna, &seq.as_bytes()[..70]);
+        // stale bits above the cut must not leak into later appends
+        dna.push_str("TTGGCA");
+        let mut expected = seq.as_bytes()[..70].to_vec();
+        expected.extend_from_slice(b"TTGGCA");
+        assert_eq!(dna, expected.as_slice());
+        // truncating at or beyond the length is a no-op
+        dna.truncate(1000);
+        assert_eq!(dna.len(), 76);
+    }
+
+    #[test]
+    fn test_as_lanes() {
+        let seq = "GATTACAGATTACA".repeat(10);
+        let dna = ColumnarDNA::from(seq.as_bytes());
+        let (store0, store1, b0, b1, len) = dna.as_lanes();
+        assert_eq!(len, seq.len());
+        assert_eq!(store0.len(), len / 64);
+        // the lanes reconstruct the sequence bit by bit
+        for (i, ch) in seq.bytes().enumerate() {
+            let (word0, word1) = if i / 64 < store0.len() {
+                (store0[i / 64], store1[i / 64])
+            } else {
+                (b0, b1)
+            };
+            let bit0 = (word0 >> (i % 64)) & 1 != 0;
+            let bit1 = (word1 >> (i % 64)) & 1 != 0;
+            assert_eq!(Nucleotide::from_bits(bit0, bit1).as_char() as u8, ch);
+        }
+    }
+
+    #[test]
+    fn test_base_positions() {
+        let mut dna = ColumnarDNA::new();
+        dna.push_str("GACGTG");
+        let g_positions: Vec<usize> = dna.base_positions(3).collect();
+        assert_eq!(g_positions, [0, 3, 5]);
+        assert_eq!(dna.base_mask(1), [0b000100]);
+
+        // across a word boundary, with partial-word garbage masked off
+        let seq = "GACGTG".repeat(20);
+        let dna = ColumnarDNA::from(seq.as_bytes());
+        for (code, base) in "ACTG".bytes().enumerate() {
+            let expected: Vec<usize> = seq
+                .bytes()
+                .enumerate()
+                .filter(|&(_, ch)| ch == base)
+                .map(|(i, _)| i)
+                .collect();
+            let positions: Vec<usize> = dna.base_positions(code as u8).collect();
+            assert_eq!(positions, expected);
+        }
+    }
+
+    #[test]
+    fn test_eq_bytes() {
+        let dna = ColumnarDNA::from(b"ACGT".as_slice());
+        assert_eq!(dna, b"ACGT".as_slice());
+        // lowercase expectations compare equal
+        assert_eq!(dna, b"acgt".as_slice());
+        assert_eq!(dna, "ACGT");
+        // a length mismatch is unequal, not a panic
+        assert_ne!(dna, b"ACG".as_slice());
+        assert_ne!(dna, b"ACGA".as_slice());
+    }
+
     #[test]
     fn test_new_starts_empty() {
         let dna = ColumnarDNA::new();
@@ -223,6 +542,15 @@ mod tests {
         assert_eq!(v.to_string(), "ACGT");
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "at most 64 bits")]
+    fn append_more_than_64_bits() {
+        let mut v = ColumnarDNA::new();
+
+        v.append(0, 0, 200);
+    }
+
     #[test]
     fn append_exact_64_bits() {
         let seq = "A".repeat(64);
@@ -275,6 +603,65 @@ mod tests {
         assert_eq!(v.to_string(), s);
     }
 
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut dna: ColumnarDNA = "ACGT".repeat(1000).bytes().collect();
+        dna.clear();
+        dna.push_str("ACGT");
+        let grown = dna.capacity();
+        dna.shrink_to_fit();
+        assert!(dna.capacity() < grown);
+        assert_eq!(dna.memory_bytes(), 0); // 4 bases still fit in the partial word
+        assert_eq!(dna.to_string(), "ACGT");
+    }
+
+    #[test]
+    fn test_to_rna_string() {
+        let dna: ColumnarDNA = "ACGT".bytes().collect();
+        assert_eq!(dna.to_rna_string(), "ACGU");
+    }
+
+    #[test]
+    fn test_collect_matches_push_str() {
+        let seq = "ACGTacgtTTTCT";
+        let collected: ColumnarDNA = seq.bytes().collect();
+        let mut pushed = ColumnarDNA::new();
+        pushed.push_str(seq);
+
+        assert_eq!(collected.len(), pushed.len());
+        assert_eq!(collected.to_string(), pushed.to_string());
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        // appends crossing the 64-bit boundary with varying chunk sizes,
+        // with deliberate garbage above `size` in the input words
+        for chunk in [1, 3, 7, 13, 31, 64] {
+            let mut v = ColumnarDNA::new();
+            for i in 0..20u64 {
+                v.append(!0, !i, chunk);
+                assert!(v.check_invariants());
+            }
+            assert_eq!(v.len(), 20 * chunk);
+        }
+
+        // clear + reuse keeps the stores consistent
+        let mut v: ColumnarDNA = "ACGT".repeat(40).bytes().collect();
+        v.clear();
+        assert!(v.check_invariants());
+        v.append(!0, !0, 64);
+        assert!(v.check_invariants());
+        assert_eq!(v.to_string(), "G".repeat(64));
+
+        let mut broken = ColumnarDNA::new();
+        broken.store0.push(0);
+        assert!(!broken.check_invariants());
+        let mut broken = ColumnarDNA::new();
+        broken.b0 = 1 << 63;
+        broken.space = 1;
+        assert!(!broken.check_invariants());
+    }
+
     #[test]
     fn cross_boundary_regression() {
         let mut v = ColumnarDNA::new();
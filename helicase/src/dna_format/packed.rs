@@ -1,6 +1,9 @@
-use std::fmt::{self, Write};
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PackedDNA {
     pub bits: Vec<u128>,
     pub num_bits: usize,
@@ -10,6 +13,8 @@ type T = u128;
 const BITS_PER_BLOCK: usize = T::BITS as usize;
 const BP_PER_BLOCK: usize = BITS_PER_BLOCK / 2;
 const PADDING: usize = 3;
+const HIGH_BITS: T = 0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA;
+const UPPERCASE_MASK: u8 = 0b1101_1111;
 
 impl PackedDNA {
     #[inline(always)]
@@ -49,8 +54,36 @@ impl PackedDNA {
         self.num_bits = 0;
     }
 
+    /// Shrink the backing storage to the blocks in use (plus padding).
+    #[inline(always)]
+    pub fn shrink_to_fit(&mut self) {
+        if self.num_bits > 0 {
+            self.bits
+                .truncate(self.num_bits.div_ceil(BITS_PER_BLOCK) + PADDING);
+        } else {
+            self.bits.truncate(0);
+        }
+        self.bits.shrink_to_fit();
+    }
+
+    /// Heap memory used by the backing storage, in bytes.
+    #[inline(always)]
+    pub fn memory_bytes(&self) -> usize {
+        self.bits.capacity() * size_of::<T>()
+    }
+
+    /// Append the low `num_bits` bits of `packed` (2 bits per base).
+    /// A single call can only carry one block, so `num_bits` must be at most
+    /// 128; this is checked with a `debug_assert`, as a miscomputed size
+    /// would silently corrupt the sequence.
+    /// The bits above `num_bits` are masked off, so callers may leave them as
+    /// garbage.
     #[inline(always)]
     pub fn append(&mut self, packed: u128, num_bits: usize) {
+        debug_assert!(
+            num_bits <= BITS_PER_BLOCK,
+            "append takes at most 128 bits per call"
+        );
         if num_bits == 0 {
             // should not happen?
             return;
@@ -69,10 +102,510 @@ impl PackedDNA {
         unsafe { *self.bits.get_unchecked_mut(idx) = x };
     }
 
+    /// Shorten the logical sequence to `len` bases, keeping the allocation.
+    /// Lengths at or beyond the current one are a no-op.
+    /// The bits above the new length are zeroed so that later
+    /// [`append`](#method.append)s do not OR into stale bits.
+    #[inline(always)]
+    pub fn truncate(&mut self, len: usize) {
+        let num_bits = 2 * len;
+        if num_bits >= self.num_bits {
+            return;
+        }
+        self.num_bits = num_bits;
+        let rem = num_bits % BITS_PER_BLOCK;
+        if rem != 0 {
+            self.bits[num_bits / BITS_PER_BLOCK] &= !0 >> (BITS_PER_BLOCK - rem);
+        }
+        for block in self.bits.iter_mut().skip(num_bits.div_ceil(BITS_PER_BLOCK)) {
+            *block = 0;
+        }
+    }
+
     #[inline(always)]
     pub fn get(&self, i: usize) -> u8 {
         ((self.bits[i / BP_PER_BLOCK] >> (2 * (i % BP_PER_BLOCK))) & 0b11) as u8
     }
+
+    /// Append a single ASCII nucleotide (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    #[inline(always)]
+    pub fn push_byte(&mut self, ch: u8) {
+        match ch {
+            b'A' | b'a' | b'C' | b'c' | b'G' | b'g' | b'T' | b't' => {}
+            _ => panic!("Invalid nucleotide: {}", ch as char),
+        }
+        self.append(((ch >> 1) & 0b11) as u128, 2);
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        for ch in s.bytes() {
+            self.push_byte(ch);
+        }
+    }
+
+    /// Mask off any bits beyond the logical length in the last used block
+    /// and zero the padding blocks, guaranteeing a clean aligned export.
+    /// [`append`](#method.append) already masks its input, but direct writes
+    /// into the public `bits` (or a shorter reuse of a grown buffer) can
+    /// leave stale bits that would leak into
+    /// [`as_raw_bytes`](#method.as_raw_bytes).
+    #[inline(always)]
+    pub fn finalize(&mut self) {
+        let rem = self.num_bits % BITS_PER_BLOCK;
+        if rem != 0 {
+            self.bits[self.num_bits / BITS_PER_BLOCK] &= !0 >> (BITS_PER_BLOCK - rem);
+        }
+        for block in self
+            .bits
+            .iter_mut()
+            .skip(self.num_bits.div_ceil(BITS_PER_BLOCK))
+        {
+            *block = 0;
+        }
+    }
+
+    /// View the meaningful packed data as raw bytes, e.g. to hand to a C/GPU kernel.
+    /// Base `i` occupies bits `2 * (i % 64)..2 * (i % 64) + 2` of block `i / 64`,
+    /// and blocks are laid out little-endian, so in the byte view base `i` lives
+    /// in byte `i / 4` at bit offset `2 * (i % 4)`.
+    /// The slice covers `ceil(len / 4)` bytes and excludes the padding blocks.
+    #[inline(always)]
+    pub fn as_raw_bytes(&self) -> &[u8] {
+        let num_bytes = self.num_bits.div_ceil(8);
+        unsafe { core::slice::from_raw_parts(self.bits.as_ptr() as *const u8, num_bytes) }
+    }
+
+    /// Reconstruct a sequence of `num_bases` bases from the byte layout of
+    /// [`as_raw_bytes`](#method.as_raw_bytes).
+    pub fn from_raw_bytes(bytes: &[u8], num_bases: usize) -> Self {
+        let num_bits = 2 * num_bases;
+        assert_eq!(bytes.len(), num_bits.div_ceil(8));
+        if num_bases == 0 {
+            return Self::new();
+        }
+        let mut bits = alloc::vec![0; num_bits.div_ceil(BITS_PER_BLOCK) + PADDING];
+        for (block, chunk) in bits.iter_mut().zip(bytes.chunks(16)) {
+            let mut buf = [0u8; 16];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            *block = u128::from_le_bytes(buf);
+        }
+        Self { bits, num_bits }
+    }
+
+    /// Remap every base to the UCSC `.2bit` code.
+    ///
+    /// The internal code is `A=0b00`, `C=0b01`, `T=0b10`, `G=0b11` (bits 1-2
+    /// of the ASCII byte), while `.2bit` uses `T=0`, `C=1`, `A=2`, `G=3`.
+    /// The two only differ by swapping the `A` and `T` codes, i.e. flipping
+    /// the high bit of every pair whose low bit is clear, so the remap is a
+    /// block-wise XOR and its own inverse.
+    /// Only the code convention is remapped: bases still occupy the
+    /// [`as_raw_bytes`](#method.as_raw_bytes) layout with the first base in
+    /// the low bits of each byte, whereas `.2bit` files put it in the high
+    /// bits.
+    pub fn to_2bit_format(&self) -> Self {
+        let mut converted = self.clone();
+        for block in &mut converted.bits {
+            *block ^= !(*block << 1) & HIGH_BITS;
+        }
+        // re-zero the bits beyond `num_bits`, which the XOR set to `A` codes
+        let used = converted.num_bits.div_ceil(BITS_PER_BLOCK);
+        for block in &mut converted.bits[used..] {
+            *block = 0;
+        }
+        let rem = converted.num_bits % BITS_PER_BLOCK;
+        if rem != 0 {
+            converted.bits[used - 1] &= !0 >> (BITS_PER_BLOCK - rem);
+        }
+        converted
+    }
+
+    /// Remap UCSC `.2bit` codes back to the internal convention.
+    /// The remap is an involution, so this is the same transform as
+    /// [`to_2bit_format`](#method.to_2bit_format).
+    #[inline(always)]
+    pub fn from_2bit_format(packed: &Self) -> Self {
+        packed.to_2bit_format()
+    }
+
+    /// Iterate over all k-mers (`k <= 32`) as 2-bit packed `u64` values,
+    /// with the first base of the k-mer in the low bits.
+    #[inline(always)]
+    pub fn kmers(&self, k: usize) -> impl Iterator<Item = u64> + '_ {
+        assert!((1..=32).contains(&k));
+        let len = self.len();
+        let mut val: u64 = 0;
+        let mut i = 0;
+        core::iter::from_fn(move || {
+            while i < len {
+                val = (val >> 2) | ((self.get(i) as u64) << (2 * (k - 1)));
+                i += 1;
+                if i >= k {
+                    return Some(val);
+                }
+            }
+            None
+        })
+    }
+
+    /// Iterate over the minimizers of the sequence: for each window of `w`
+    /// consecutive k-mers, the `(position, value)` of its smallest k-mer
+    /// (leftmost on ties), in O(n) via a monotonic deque.
+    /// With `canonical`, each k-mer is replaced by the smaller of itself and
+    /// its reverse complement before comparison.
+    pub fn minimizers(
+        &self,
+        k: usize,
+        w: usize,
+        canonical: bool,
+    ) -> impl Iterator<Item = (usize, u64)> + '_ {
+        assert!(w >= 1);
+        let mut kmers = self.kmers(k).enumerate();
+        let mut deque: VecDeque<(usize, u64)> = VecDeque::new();
+        core::iter::from_fn(move || {
+            for (i, mut val) in kmers.by_ref() {
+                if canonical {
+                    val = val.min(revcomp_kmer(val, k));
+                }
+                // strict comparison keeps the leftmost k-mer on ties
+                while deque.back().is_some_and(|&(_, v)| v > val) {
+                    deque.pop_back();
+                }
+                deque.push_back((i, val));
+                if i + 1 >= w {
+                    while deque.front().is_some_and(|&(p, _)| p + w <= i) {
+                        deque.pop_front();
+                    }
+                    return Some(*deque.front().unwrap());
+                }
+            }
+            None
+        })
+    }
+
+    /// Count the occurrences of a base given its 2-bit code
+    /// (`A = 0`, `C = 1`, `T = 2`, `G = 3`), one XOR and popcount per block.
+    #[inline(always)]
+    pub fn count_base(&self, code: u8) -> usize {
+        const LOW_OF_PAIR: u128 = 0x5555_5555_5555_5555_5555_5555_5555_5555;
+        let pattern = LOW_OF_PAIR * (code & 0b11) as u128; // code broadcast to every 2-bit lane
+        let mut count = 0;
+        for i in 0..self.num_bits.div_ceil(BITS_PER_BLOCK) {
+            let x = self.bits[i] ^ pattern;
+            // a base matches if neither of its two bits differs
+            let mut eq = !(x | (x >> 1)) & LOW_OF_PAIR;
+            let rem = self.num_bits - i * BITS_PER_BLOCK;
+            if rem < BITS_PER_BLOCK {
+                eq &= !0 >> (BITS_PER_BLOCK - rem);
+            }
+            count += eq.count_ones() as usize;
+        }
+        count
+    }
+
+    /// Render the sequence as RNA, emitting `U` where [`Display`](fmt::Display) emits `T`.
+    /// This is only a display substitution, the 2-bit encoding is unchanged.
+    pub fn to_rna_string(&self) -> String {
+        const LUT: [char; 4] = ['A', 'C', 'U', 'G'];
+        let mut s = String::with_capacity(self.len());
+        for i in 0..self.len() {
+            s.push(LUT[self.get(i) as usize]);
+        }
+        s
+    }
+
+    /// Iterate over homopolymer runs as `(base_code, run_length)` pairs,
+    /// without decoding to ASCII.
+    /// Base codes follow the 2-bit encoding (`A = 0`, `C = 1`, `T = 2`, `G = 3`).
+    #[inline(always)]
+    pub fn homopolymer_runs(&self) -> impl Iterator<Item = (u8, usize)> + '_ {
+        let len = self.len();
+        let mut i = 0;
+        core::iter::from_fn(move || {
+            if i >= len {
+                return None;
+            }
+            let base = self.get(i);
+            let start = i;
+            i += 1;
+            while i < len && self.get(i) == base {
+                i += 1;
+            }
+            Some((base, i - start))
+        })
+    }
+
+    /// Running `G` and `C` counts (and window length) for windows of `window`
+    /// bases starting every `step` bases; the final window may be shorter.
+    /// The counts are updated incrementally as the window slides, one base at
+    /// a time, instead of re-counting each window.
+    fn windowed_gc_counts(
+        &self,
+        window: usize,
+        step: usize,
+    ) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        assert!(window >= 1);
+        assert!(step >= 1);
+        let len = self.len();
+        let (mut g, mut c) = (0usize, 0usize);
+        // counted range, kept in sync with the counts
+        let (mut start, mut end) = (0usize, 0usize);
+        let mut next = 0;
+        core::iter::from_fn(move || {
+            if next >= len {
+                return None;
+            }
+            let s = next;
+            next += step;
+            if s >= end {
+                // disjoint windows (step > window): restart the counts
+                (g, c, start, end) = (0, 0, s, s);
+            }
+            for i in start..s {
+                match self.get(i) {
+                    0b11 => g -= 1,
+                    0b01 => c -= 1,
+                    _ => {}
+                }
+            }
+            start = s;
+            for i in end..(s + window).min(len) {
+                match self.get(i) {
+                    0b11 => g += 1,
+                    0b01 => c += 1,
+                    _ => {}
+                }
+            }
+            end = (s + window).min(len);
+            Some((g, c, end - s))
+        })
+    }
+
+    /// GC content of sliding windows of `window` bases, advancing by `step`;
+    /// the final window may be shorter and is averaged over its actual length.
+    pub fn windowed_gc(&self, window: usize, step: usize) -> Vec<f32> {
+        self.windowed_gc_counts(window, step)
+            .map(|(g, c, len)| (g + c) as f32 / len as f32)
+            .collect()
+    }
+
+    /// GC skew `(G - C) / (G + C)` of sliding windows of `window` bases,
+    /// advancing by `step`; windows without any `G` or `C` report `0.0`.
+    pub fn windowed_gc_skew(&self, window: usize, step: usize) -> Vec<f32> {
+        self.windowed_gc_counts(window, step)
+            .map(|(g, c, _)| {
+                if g + c == 0 {
+                    0.0
+                } else {
+                    (g as f32 - c as f32) / (g + c) as f32
+                }
+            })
+            .collect()
+    }
+
+    /// Decode the whole sequence to ASCII, appending to `out`.
+    /// This expands the 2-bit codes in bulk (16 bases at a time with SIMD when
+    /// available) instead of calling [`get`](#method.get) per base.
+    #[inline(always)]
+    pub fn decode_into(&self, out: &mut Vec<u8>) {
+        let mut remaining = self.len();
+        out.reserve(remaining);
+        for &block in &self.bits {
+            if remaining == 0 {
+                break;
+            }
+            let mut buf = [0u8; BP_PER_BLOCK];
+            decode_block(block, &mut buf);
+            let n = remaining.min(BP_PER_BLOCK);
+            out.extend_from_slice(&buf[..n]);
+            remaining -= n;
+        }
+    }
+
+    /// Count the number of differing bases between two equal-length sequences.
+    /// Returns `None` if the lengths differ.
+    #[inline(always)]
+    pub fn hamming(&self, other: &PackedDNA) -> Option<usize> {
+        if self.num_bits != other.num_bits {
+            return None;
+        }
+        const LOW_OF_PAIR: u128 = 0x5555_5555_5555_5555_5555_5555_5555_5555;
+        let mut count = 0;
+        for i in 0..self.num_bits.div_ceil(BITS_PER_BLOCK) {
+            let mut x = self.bits[i] ^ other.bits[i];
+            let rem = self.num_bits - i * BITS_PER_BLOCK;
+            if rem < BITS_PER_BLOCK {
+                x &= !0 >> (BITS_PER_BLOCK - rem);
+            }
+            // a base differs if either of its two bits differs
+            count += ((x | (x >> 1)) & LOW_OF_PAIR).count_ones() as usize;
+        }
+        Some(count)
+    }
+}
+
+/// Decode one block of 64 packed bases to ASCII.
+#[cfg(target_feature = "avx2")]
+#[inline(always)]
+fn decode_block(block: u128, out: &mut [u8; BP_PER_BLOCK]) {
+    use core::arch::x86_64::*;
+    const LUT_BASES: __m256i =
+        unsafe { core::mem::transmute::<[u8; 32], __m256i>(*b"ACTG............ACTG............") };
+    // one byte per 2-bit lane, 8 bases per pdep
+    const BYTE_CODES: u64 = 0x0303030303030303;
+    unsafe {
+        let lo = block as u64;
+        let hi = (block >> 64) as u64;
+        let v1 = core::mem::transmute::<[u64; 4], __m256i>([
+            _pdep_u64(lo, BYTE_CODES),
+            _pdep_u64(lo >> 16, BYTE_CODES),
+            _pdep_u64(lo >> 32, BYTE_CODES),
+            _pdep_u64(lo >> 48, BYTE_CODES),
+        ]);
+        let v2 = core::mem::transmute::<[u64; 4], __m256i>([
+            _pdep_u64(hi, BYTE_CODES),
+            _pdep_u64(hi >> 16, BYTE_CODES),
+            _pdep_u64(hi >> 32, BYTE_CODES),
+            _pdep_u64(hi >> 48, BYTE_CODES),
+        ]);
+        let out_ptr = out.as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(out_ptr, _mm256_shuffle_epi8(LUT_BASES, v1));
+        _mm256_storeu_si256(out_ptr.add(1), _mm256_shuffle_epi8(LUT_BASES, v2));
+    }
+}
+
+/// Decode one block of 64 packed bases to ASCII.
+#[cfg(target_feature = "neon")]
+#[inline(always)]
+fn decode_block(block: u128, out: &mut [u8; BP_PER_BLOCK]) {
+    use core::arch::aarch64::*;
+    const LUT_BASES: uint8x16_t =
+        unsafe { core::mem::transmute::<[u8; 16], uint8x16_t>(*b"ACTG............") };
+    // repeat each source byte four times, then shift its four codes into place
+    const DUP_IDX: uint8x16_t = unsafe {
+        core::mem::transmute::<[u8; 16], uint8x16_t>([
+            0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3,
+        ])
+    };
+    const SHIFTS: int8x16_t = unsafe {
+        core::mem::transmute::<[i8; 16], int8x16_t>([
+            0, -2, -4, -6, 0, -2, -4, -6, 0, -2, -4, -6, 0, -2, -4, -6,
+        ])
+    };
+    const TWO_BITS: uint8x16_t =
+        unsafe { core::mem::transmute::<[u8; 16], uint8x16_t>([0b11; 16]) };
+    unsafe {
+        for (j, chunk) in out.chunks_exact_mut(16).enumerate() {
+            let src = (block >> (32 * j)) as u32;
+            let dup = vqtbl1q_u8(vreinterpretq_u8_u32(vdupq_n_u32(src)), DUP_IDX);
+            let codes = vandq_u8(vshlq_u8(dup, SHIFTS), TWO_BITS);
+            vst1q_u8(chunk.as_mut_ptr(), vqtbl1q_u8(LUT_BASES, codes));
+        }
+    }
+}
+
+/// Decode one block of 64 packed bases to ASCII.
+#[cfg(not(any(target_feature = "avx2", target_feature = "neon")))]
+#[inline(always)]
+fn decode_block(block: u128, out: &mut [u8; BP_PER_BLOCK]) {
+    const LUT: [u8; 4] = *b"ACTG";
+    for (i, x) in out.iter_mut().enumerate() {
+        *x = LUT[((block >> (2 * i)) & 0b11) as usize];
+    }
+}
+
+/// Reverse complement of a 2-bit packed k-mer.
+#[inline(always)]
+fn revcomp_kmer(val: u64, k: usize) -> u64 {
+    // A<->T and C<->G toggle the high bit of each 2-bit lane
+    let comp = val ^ 0xAAAA_AAAA_AAAA_AAAA;
+    // reverse all bits, then swap adjacent bits to restore the lanes
+    let rev = comp.reverse_bits();
+    let rev = ((rev >> 1) & 0x5555_5555_5555_5555) | ((rev & 0x5555_5555_5555_5555) << 1);
+    rev >> (64 - 2 * k)
+}
+
+/// Compare the decoded bases to an expected ASCII sequence,
+/// case-insensitively; a length mismatch compares as unequal.
+impl PartialEq<[u8]> for PackedDNA {
+    fn eq(&self, other: &[u8]) -> bool {
+        const BASES: [u8; 4] = [b'A', b'C', b'T', b'G'];
+        self.len() == other.len()
+            && other
+                .iter()
+                .enumerate()
+                .all(|(i, &ch)| BASES[self.get(i) as usize] == ch & UPPERCASE_MASK)
+    }
+}
+
+impl PartialEq<&[u8]> for PackedDNA {
+    fn eq(&self, other: &&[u8]) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<&str> for PackedDNA {
+    fn eq(&self, other: &&str) -> bool {
+        *self == *other.as_bytes()
+    }
+}
+
+impl From<&[u8]> for PackedDNA {
+    /// Convert from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(seq: &[u8]) -> Self {
+        let mut res = Self::with_capacity(seq.len());
+        res.extend(seq.iter().copied());
+        res
+    }
+}
+
+#[cfg(feature = "needletail")]
+impl From<&needletail::parser::SequenceRecord<'_>> for PackedDNA {
+    /// Convert the sequence of a needletail record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &needletail::parser::SequenceRecord<'_>) -> Self {
+        Self::from(record.seq().as_ref())
+    }
+}
+
+#[cfg(feature = "bio")]
+impl From<&bio::io::fasta::Record> for PackedDNA {
+    /// Convert the sequence of a rust-bio FASTA record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &bio::io::fasta::Record) -> Self {
+        Self::from(record.seq())
+    }
+}
+
+#[cfg(feature = "bio")]
+impl From<&bio::io::fastq::Record> for PackedDNA {
+    /// Convert the sequence of a rust-bio FASTQ record, to ease incremental adoption.
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from(record: &bio::io::fastq::Record) -> Self {
+        Self::from(record.seq())
+    }
+}
+
+impl Extend<u8> for PackedDNA {
+    /// Extend from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        for ch in iter {
+            self.push_byte(ch);
+        }
+    }
+}
+
+impl FromIterator<u8> for PackedDNA {
+    /// Collect from ASCII nucleotides (case-insensitive).
+    /// This panics on bytes other than `A`/`C`/`G`/`T`.
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
 }
 
 impl fmt::Display for PackedDNA {
@@ -84,3 +617,331 @@ impl fmt::Display for PackedDNA {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // only uses `core`/`alloc` items, so it also compiles with `--no-default-features`
+    #[test]
+    fn test_append_no_std() {
+        use alloc::string::ToString;
+        let mut dna = PackedDNA::new();
+        dna.append(0b11_10_01_00, 8); // ACTG, 2 bits per base
+        assert_eq!(dna.len(), 4);
+        assert_eq!(dna.to_string(), "ACTG");
+    }
+
+    #[test]
+    fn test_finalize_masks_trailing_bits() {
+        use alloc::string::ToString;
+        let mut dna = PackedDNA::new();
+        // staggered appends crossing the 128-bit block boundary
+        dna.append(0b11_10_01_00, 8);
+        dna.push_str(&"GATTACA".repeat(12));
+        dna.push_str("GAT");
+        // scribble into the public storage beyond the logical length
+        let last = dna.num_bits / BITS_PER_BLOCK;
+        dna.bits[last] |= !0 << (dna.num_bits % BITS_PER_BLOCK);
+        *dna.bits.last_mut().unwrap() = !0;
+        dna.finalize();
+        assert_eq!(dna.bits[last] >> (dna.num_bits % BITS_PER_BLOCK), 0);
+        assert_eq!(*dna.bits.last().unwrap(), 0);
+        // the logical content is untouched
+        assert_eq!(dna.to_string(), format!("ACTG{}GAT", "GATTACA".repeat(12)));
+        // and the raw byte view ends with clean trailing bits
+        let raw = dna.as_raw_bytes();
+        assert_eq!(raw.last().unwrap() >> (dna.num_bits % 8), 0);
+    }
+
+    #[test]
+    fn test_collect_matches_push_str() {
+        let seq = "ACGTacgtTTTCT";
+        let collected: PackedDNA = seq.bytes().collect();
+        let mut pushed = PackedDNA::new();
+        pushed.push_str(seq);
+
+        assert_eq!(collected.len(), seq.len());
+        assert_eq!(collected.to_string(), "ACGTACGTTTTCT");
+        assert_eq!(collected.to_string(), pushed.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid nucleotide")]
+    fn test_collect_invalid_base_panics() {
+        let _: PackedDNA = b"ACNT".iter().copied().collect();
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut dna: PackedDNA = "ACGT".repeat(1000).bytes().collect();
+        dna.clear();
+        dna.push_str("ACGT");
+        let grown = dna.capacity();
+        dna.shrink_to_fit();
+        assert!(dna.capacity() < grown);
+        assert!(dna.memory_bytes() <= grown * size_of::<u128>());
+        assert_eq!(dna.to_string(), "ACGT");
+    }
+
+    #[test]
+    fn test_raw_bytes_round_trip() {
+        // A=00, C=01, G=11, T=10 => first byte 0b10_11_01_00
+        let dna: PackedDNA = "ACGT".bytes().collect();
+        assert_eq!(dna.as_raw_bytes(), [0b10_11_01_00]);
+        assert_eq!(
+            PackedDNA::from_raw_bytes(dna.as_raw_bytes(), dna.len()),
+            dna
+        );
+
+        // cross the u128 block boundary
+        let long: PackedDNA = "ACGT".repeat(33).bytes().collect();
+        assert_eq!(long.as_raw_bytes().len(), 33);
+        assert_eq!(
+            PackedDNA::from_raw_bytes(long.as_raw_bytes(), long.len()),
+            long
+        );
+    }
+
+    #[test]
+    fn test_kmers() {
+        let dna: PackedDNA = "ACGT".bytes().collect();
+        // A=0, C=1, G=3, T=2, first base in the low bits
+        let kmers: Vec<u64> = dna.kmers(3).collect();
+        assert_eq!(kmers, [0b11_01_00, 0b10_11_01]); // ACG, CGT
+    }
+
+    #[test]
+    fn test_revcomp_kmer() {
+        let dna: PackedDNA = "ACG".bytes().collect();
+        let rc: PackedDNA = "CGT".bytes().collect();
+        assert_eq!(
+            revcomp_kmer(dna.kmers(3).next().unwrap(), 3),
+            rc.kmers(3).next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_minimizers() {
+        let dna: PackedDNA = "ACGTACGTACGT".bytes().collect();
+        let (k, w) = (3, 2);
+
+        for canonical in [false, true] {
+            let kmers: Vec<u64> = dna
+                .kmers(k)
+                .map(|v| {
+                    if canonical {
+                        v.min(revcomp_kmer(v, k))
+                    } else {
+                        v
+                    }
+                })
+                .collect();
+            // brute force: leftmost smallest k-mer of each window
+            let expected: Vec<(usize, u64)> = kmers
+                .windows(w)
+                .enumerate()
+                .map(|(start, win)| {
+                    let (off, &val) = win
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|&(off, &val)| (val, off))
+                        .unwrap();
+                    (start + off, val)
+                })
+                .collect();
+            let got: Vec<(usize, u64)> = dna.minimizers(k, w, canonical).collect();
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn test_count_base() {
+        let dna: PackedDNA = "AATTCCGG".bytes().collect();
+        assert_eq!(dna.count_base(0), 2); // A
+        assert_eq!(dna.count_base(1), 2); // C
+        assert_eq!(dna.count_base(2), 2); // T
+        assert_eq!(dna.count_base(3), 2); // G
+
+        // partial trailing block must not count padding as A
+        let all_c: PackedDNA = "C".repeat(65).bytes().collect();
+        assert_eq!(all_c.count_base(0), 0);
+        assert_eq!(all_c.count_base(1), 65);
+    }
+
+    #[test]
+    fn test_windowed_gc() {
+        let dna: PackedDNA = "GGGGCCCCAAAATTTT".bytes().collect();
+        assert_eq!(dna.windowed_gc(4, 4), [1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(dna.windowed_gc_skew(4, 4), [1.0, -1.0, 0.0, 0.0]);
+
+        // overlapping windows, with a shorter final window
+        assert_eq!(dna.windowed_gc(8, 4), [1.0, 0.5, 0.0, 0.0]);
+        assert_eq!(dna.windowed_gc_skew(8, 4), [0.0, -1.0, 0.0, 0.0]);
+
+        // disjoint windows (step > window)
+        assert_eq!(dna.windowed_gc(2, 6), [1.0, 1.0, 0.0]);
+
+        // the incremental counts must match a per-window recount
+        let dna: PackedDNA = (0..100).map(|i| b"ACGGTGC"[i * i % 7]).collect();
+        let gc = dna.windowed_gc(7, 3);
+        assert_eq!(gc.len(), 100usize.div_ceil(3));
+        for (&gc, s) in gc.iter().zip((0..100).step_by(3)) {
+            let window: Vec<u8> = (s..(s + 7).min(100)).map(|i| dna.get(i)).collect();
+            let count = window.iter().filter(|&&b| b == 0b11 || b == 0b01).count();
+            assert_eq!(gc, count as f32 / window.len() as f32);
+        }
+    }
+
+    #[test]
+    fn test_decode_into() {
+        use alloc::string::ToString;
+
+        // 1000 bp, crossing several block boundaries with a partial last block
+        let dna: PackedDNA = (0..1000).map(|i| b"ACGGTGC"[i * i % 7]).collect();
+        let mut out = Vec::new();
+        dna.decode_into(&mut out);
+        assert_eq!(String::from_utf8(out).unwrap(), dna.to_string());
+
+        // appends to the output without clearing it
+        let mut out = b"N".to_vec();
+        let short: PackedDNA = "ACGT".bytes().collect();
+        short.decode_into(&mut out);
+        assert_eq!(out, b"NACGT");
+
+        PackedDNA::new().decode_into(&mut out);
+        assert_eq!(out, b"NACGT");
+    }
+
+    #[test]
+    fn test_to_rna_string() {
+        let dna: PackedDNA = "ACGT".bytes().collect();
+        assert_eq!(dna.to_rna_string(), "ACGU");
+    }
+
+    #[test]
+    fn test_homopolymer_runs() {
+        let dna: PackedDNA = "AAACCG".bytes().collect();
+        let runs: Vec<(u8, usize)> = dna.homopolymer_runs().collect();
+        assert_eq!(runs, [(0, 3), (1, 2), (3, 1)]); // A=0, C=1, G=3
+
+        assert_eq!(PackedDNA::new().homopolymer_runs().count(), 0);
+
+        // a run crossing the u128 block boundary stays a single run
+        let long: PackedDNA = "A".repeat(100).bytes().collect();
+        let runs: Vec<(u8, usize)> = long.homopolymer_runs().collect();
+        assert_eq!(runs, [(0, 100)]);
+    }
+
+    #[test]
+    fn test_hamming() {
+        let a: PackedDNA = "ACGT".bytes().collect();
+        let b: PackedDNA = "ACGA".bytes().collect();
+        assert_eq!(a.hamming(&b), Some(1));
+        assert_eq!(a.hamming(&a), Some(0));
+
+        let short: PackedDNA = "ACG".bytes().collect();
+        assert_eq!(a.hamming(&short), None);
+
+        // cross a block boundary
+        let long_a: PackedDNA = "ACGT".repeat(20).bytes().collect();
+        let mut long_b: PackedDNA = "ACGT".repeat(19).bytes().collect();
+        long_b.push_str("TCGT");
+        assert_eq!(long_a.hamming(&long_b), Some(1));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "at most 128 bits")]
+    fn append_more_than_128_bits() {
+        let mut packed = PackedDNA::new();
+        packed.append(0, 129);
+    }
+
+    #[test]
+    fn test_append_masks_garbage() {
+        let mut packed = PackedDNA::new();
+        // the bits above `num_bits` are ignored
+        packed.append(!0, 2);
+        packed.append(!0 << 2, 2);
+        assert_eq!(packed, "GA");
+    }
+
+    #[test]
+    fn test_truncate() {
+        let seq = "ACGT".repeat(25);
+        let mut packed = PackedDNA::from(seq.as_bytes());
+        // a non-block-aligned cut leaves a partial block
+        packed.truncate(70);
+        assert_eq!(packed.len(), 70);
+        assert_eq!(packed, &seq.as_bytes()[..70]);
+        // stale bits above the cut must not leak into later appends
+        packed.push_str("TTGGCA");
+        let mut expected = seq.as_bytes()[..70].to_vec();
+        expected.extend_from_slice(b"TTGGCA");
+        assert_eq!(packed, expected.as_slice());
+        assert_eq!(packed, PackedDNA::from(expected.as_slice()));
+        // truncating at or beyond the length is a no-op
+        packed.truncate(1000);
+        assert_eq!(packed.len(), 76);
+    }
+
+    #[test]
+    fn test_eq_bytes() {
+        let packed = PackedDNA::from(b"ACGT".as_slice());
+        assert_eq!(packed, b"ACGT".as_slice());
+        // lowercase expectations compare equal
+        assert_eq!(packed, b"acgt".as_slice());
+        assert_eq!(packed, "ACGT");
+        // a length mismatch is unequal, not a panic
+        assert_ne!(packed, b"ACG".as_slice());
+        assert_ne!(packed, b"ACGTA".as_slice());
+        assert_ne!(packed, b"ACGA".as_slice());
+    }
+
+    #[test]
+    fn test_2bit_format() {
+        let mut packed = PackedDNA::new();
+        packed.push_str("TCAG");
+        let converted = packed.to_2bit_format();
+        // `T C A G` map to codes 0, 1, 2, 3, packed with the first base in
+        // the low bits of the byte
+        assert_eq!(converted.as_raw_bytes()[0], 0b11100100);
+        assert_eq!(PackedDNA::from_2bit_format(&converted), packed);
+
+        // round-trip across block boundaries
+        let mut packed = PackedDNA::new();
+        packed.push_str(&"ACGTTGCAATCG".repeat(20));
+        assert_eq!(
+            PackedDNA::from_2bit_format(&packed.to_2bit_format()),
+            packed
+        );
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let packed = PackedDNA::from(b"ACGTacgt".as_slice());
+        assert_eq!(format!("{packed}"), "ACGTACGT");
+    }
+
+    #[cfg(feature = "needletail")]
+    #[test]
+    fn test_from_needletail() {
+        let mut reader = needletail::parse_fastx_reader(&b">r\nACGTACGT\n"[..]).unwrap();
+        let record = reader.next().unwrap().unwrap();
+        let packed = PackedDNA::from(&record);
+        assert_eq!(format!("{packed}"), "ACGTACGT");
+        let columnar = crate::dna_format::ColumnarDNA::from(&record);
+        assert_eq!(format!("{columnar}"), "ACGTACGT");
+    }
+
+    #[cfg(feature = "bio")]
+    #[test]
+    fn test_from_bio() {
+        let record = bio::io::fasta::Record::with_attrs("r", None, b"ACGTACGT");
+        let packed = PackedDNA::from(&record);
+        assert_eq!(format!("{packed}"), "ACGTACGT");
+        let columnar = crate::dna_format::ColumnarDNA::from(&record);
+        assert_eq!(format!("{columnar}"), "ACGTACGT");
+    }
+}
@@ -1,13 +1,17 @@
 //! Input formats and helpers.
 
+use crate::CHUNK_WIDTH;
 use core::marker::PhantomData;
 use deko::read::AnyDecoder;
 use memmap2::Mmap;
 use std::fs::File;
-use std::io::{self, Read, Stdin, stdin};
+use std::io::{self, BufRead, Read, Stdin, stdin};
+#[cfg(feature = "gz")]
+use std::io::{Seek, SeekFrom};
 use std::path::Path;
 
 const DEFAULT_BUFFER_SIZE: usize = 1 << 16;
+pub(crate) const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
 
 pub trait InputData<'a>: Iterator<Item = &'a [u8]> {
     const RANDOM_ACCESS: bool;
@@ -31,6 +35,12 @@ pub trait InputData<'a>: Iterator<Item = &'a [u8]> {
     /// This is faster than calling `current_chunk().len()`.
     fn current_chunk_len(&self) -> usize;
 
+    /// Returns the offset of the first byte of the current chunk, counted from
+    /// the start of the (decompressed) input.
+    ///
+    /// For random-access implementations this is an index into [`data`](Self::data).
+    fn chunk_offset(&self) -> usize;
+
     /// Get a reference to the internal buffer.
     ///
     /// This is only relevant for reader-based implementations.
@@ -57,6 +67,19 @@ pub trait InputData<'a>: Iterator<Item = &'a [u8]> {
     /// Returns the first byte of the (uncompressed when possible) input.
     fn first_byte(&self) -> u8;
 
+    /// Returns up to `n` of the first bytes of the (decompressed when
+    /// possible) input, for format sniffing.
+    /// Reader-based implementations may return fewer bytes than requested.
+    #[inline(always)]
+    fn first_bytes(&self, n: usize) -> &[u8] {
+        let data = if Self::RANDOM_ACCESS {
+            self.data()
+        } else {
+            self.buffer()
+        };
+        &data[..n.min(data.len())]
+    }
+
     /// Returns the type of compression format detected.
     ///
     /// This is only available for reader-based implementations.
@@ -79,18 +102,24 @@ pub trait FromInputData<'a, I: InputData<'a>>: Sized {
 
 /// Slice input.
 /// It supports parallel processing, but not transparent decompression.
+#[derive(Clone)]
 pub struct SliceInput<'a> {
     data: &'a [u8],
     pos: usize,
     first_byte: u8,
-    last_chunk: [u8; 64],
+    last_chunk: [u8; CHUNK_WIDTH],
 }
 
 impl<'a> SliceInput<'a> {
-    pub fn new(data: &'a [u8]) -> Self {
+    pub fn new(mut data: &'a [u8]) -> Self {
+        // skip a leading UTF-8 BOM so that offsets are relative to the actual content
+        if data.starts_with(&UTF8_BOM) {
+            data = &data[UTF8_BOM.len()..];
+        }
         assert!(!data.is_empty());
-        let mut last_chunk = [0; 64];
-        last_chunk[..data.len() % 64].copy_from_slice(&data[(data.len() / 64) * 64..]);
+        let mut last_chunk = [0; CHUNK_WIDTH];
+        last_chunk[..data.len() % CHUNK_WIDTH]
+            .copy_from_slice(&data[(data.len() / CHUNK_WIDTH) * CHUNK_WIDTH..]);
         Self {
             data,
             pos: 0,
@@ -106,14 +135,19 @@ impl<'a> Iterator for SliceInput<'a> {
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         let pos = self.pos;
-        self.pos += 64;
-        if pos + 64 <= self.data.len() {
-            unsafe { Some(std::slice::from_raw_parts(self.data.as_ptr().add(pos), 64)) }
+        self.pos += CHUNK_WIDTH;
+        if pos + CHUNK_WIDTH <= self.data.len() {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    CHUNK_WIDTH,
+                ))
+            }
         } else if pos < self.data.len() {
             unsafe {
                 Some(std::slice::from_raw_parts(
                     self.last_chunk.as_ptr(),
-                    self.data.len() % 64,
+                    self.data.len() % CHUNK_WIDTH,
                 ))
             }
         } else {
@@ -132,19 +166,35 @@ impl<'a> InputData<'a> for SliceInput<'a> {
 
     #[inline(always)]
     fn current_chunk(&self) -> &[u8] {
-        if 64 <= self.pos && self.pos <= self.data.len() {
-            unsafe { std::slice::from_raw_parts(self.data.as_ptr().add(self.pos - 64), 64) }
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.data.len() {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data.as_ptr().add(self.pos - CHUNK_WIDTH),
+                    CHUNK_WIDTH,
+                )
+            }
         } else {
-            unsafe { std::slice::from_raw_parts(self.last_chunk.as_ptr(), self.data.len() % 64) }
+            unsafe {
+                std::slice::from_raw_parts(self.last_chunk.as_ptr(), self.data.len() % CHUNK_WIDTH)
+            }
         }
     }
 
     #[inline(always)]
     fn current_chunk_len(&self) -> usize {
         if self.pos <= self.data.len() {
-            64
+            CHUNK_WIDTH
+        } else {
+            self.data.len() % CHUNK_WIDTH
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.data.len() {
+            self.pos - CHUNK_WIDTH
         } else {
-            self.data.len() % 64
+            (self.data.len() / CHUNK_WIDTH) * CHUNK_WIDTH
         }
     }
 
@@ -171,6 +221,23 @@ pub trait FromSlice<'a>: FromInputData<'a, SliceInput<'a>> {
     fn from_slice(data: &'a [u8]) -> Self {
         Self::from_input(SliceInput::new(data))
     }
+
+    /// Build the struct from a string, sparing the `.as_bytes()` in tests
+    /// and small scripts.
+    /// ```
+    /// use helicase::input::FromSlice;
+    /// use helicase::*;
+    ///
+    /// const CONFIG: Config = ParserOptions::default().config();
+    /// let mut parser = FastaParser::<CONFIG, _>::from_str(">h\nACGT");
+    /// assert!(parser.next().is_some());
+    /// assert_eq!(parser.get_header(), b"h");
+    /// assert_eq!(parser.get_dna_string(), b"ACGT");
+    /// ```
+    #[inline(always)]
+    fn from_str(s: &'a str) -> Self {
+        Self::from_slice(s.as_bytes())
+    }
 }
 
 impl<'a, F: FromInputData<'a, SliceInput<'a>>> FromSlice<'a> for F {}
@@ -179,10 +246,17 @@ impl<'a, F: FromInputData<'a, SliceInput<'a>>> FromSlice<'a> for F {}
 /// It supports parallel processing, but not transparent decompression.
 pub struct MmapInput<'a> {
     slice: SliceInput<'a>,
+    file: File,
     _mmap: Mmap,
 }
 
 impl<'a> MmapInput<'a> {
+    /// Map `path` into memory.
+    /// The mapped length is fixed at map time: data appended to a live file
+    /// (e.g. a sequencer still writing) is not seen, and truncating the file
+    /// while it is mapped can fault on access. For a file that may change
+    /// underneath, prefer [`new_checked`](Self::new_checked) or a reader
+    /// input.
     pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         // Unsafe: mmap are intrisically unsafe.
         // Here we add on the top of that a slice of [u8] that will live as long as file is not dropped.
@@ -192,9 +266,46 @@ impl<'a> MmapInput<'a> {
         let data = unsafe { std::slice::from_raw_parts(_mmap.as_ptr(), _mmap.len()) };
         Ok(Self {
             slice: SliceInput::new(data),
+            file,
             _mmap,
         })
     }
+
+    /// Like [`new`](Self::new), but refuse a file whose size already changed
+    /// between opening and mapping, catching the most blatant live-file
+    /// mistakes up front.
+    /// Re-check with [`is_complete_file`](Self::is_complete_file) before
+    /// trusting offsets computed from the map.
+    pub fn new_checked<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let input = Self::new(path)?;
+        if !input.is_complete_file()? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file size changed while mapping; is it still being written to?",
+            ));
+        }
+        Ok(input)
+    }
+
+    /// The mapped length in bytes, fixed at map time.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.data().len()
+    }
+
+    /// Whether the mapping is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.data().is_empty()
+    }
+
+    /// Whether the mapping still covers the whole file, by comparing the
+    /// mapped length against the file's current size.
+    /// `false` means the file grew (the extra data is not mapped) or shrank
+    /// (reads past the new end can fault).
+    pub fn is_complete_file(&self) -> io::Result<bool> {
+        Ok(self.file.metadata()?.len() as usize == self.len())
+    }
 }
 
 impl<'a> Iterator for MmapInput<'a> {
@@ -224,6 +335,11 @@ impl<'a> InputData<'a> for MmapInput<'a> {
         self.slice.current_chunk_len()
     }
 
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.slice.chunk_offset()
+    }
+
     #[inline(always)]
     fn buffer(&self) -> &[u8] {
         self.slice.buffer()
@@ -296,6 +412,11 @@ impl InputData<'static> for RamFileInput {
         self.slice.current_chunk_len()
     }
 
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.slice.chunk_offset()
+    }
+
     #[inline(always)]
     fn buffer(&self) -> &[u8] {
         self.slice.buffer()
@@ -323,6 +444,82 @@ pub trait FromRamFile: FromInputData<'static, RamFileInput> {
 
 impl<F: FromInputData<'static, RamFileInput>> FromRamFile for F {}
 
+/// Owned in-memory input.
+/// It supports parallel processing, but not transparent decompression.
+pub struct VecInput {
+    slice: SliceInput<'static>,
+    _vec: Vec<u8>,
+}
+
+impl VecInput {
+    pub fn new(vec: Vec<u8>) -> Self {
+        let data = unsafe { std::slice::from_raw_parts(vec.as_ptr(), vec.len()) };
+        Self {
+            slice: SliceInput::new(data),
+            _vec: vec,
+        }
+    }
+}
+
+impl Iterator for VecInput {
+    type Item = &'static [u8];
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slice.next()
+    }
+}
+
+impl InputData<'static> for VecInput {
+    const RANDOM_ACCESS: bool = true;
+
+    #[inline(always)]
+    fn data(&self) -> &[u8] {
+        self.slice.data()
+    }
+
+    #[inline(always)]
+    fn current_chunk(&self) -> &[u8] {
+        self.slice.current_chunk()
+    }
+
+    #[inline(always)]
+    fn current_chunk_len(&self) -> usize {
+        self.slice.current_chunk_len()
+    }
+
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.slice.chunk_offset()
+    }
+
+    #[inline(always)]
+    fn buffer(&self) -> &[u8] {
+        self.slice.buffer()
+    }
+
+    #[inline(always)]
+    fn is_end_of_buffer(&self) -> bool {
+        self.slice.is_end_of_buffer()
+    }
+
+    #[inline(always)]
+    fn first_byte(&self) -> u8 {
+        self.slice.first_byte()
+    }
+}
+
+pub trait FromVec: FromInputData<'static, VecInput> {
+    /// Build the struct from an owned `Vec<u8>` already in memory.
+    /// It supports parallel processing, but not transparent decompression.
+    #[inline(always)]
+    fn from_vec(vec: Vec<u8>) -> Self {
+        Self::from_input(VecInput::new(vec))
+    }
+}
+
+impl<F: FromInputData<'static, VecInput>> FromVec for F {}
+
 /// Reader input.
 /// It supports transparent decompression, but not parallel processing.
 pub struct ReaderInput<'a, R: Read + Send + 'a> {
@@ -332,6 +529,7 @@ pub struct ReaderInput<'a, R: Read + Send + 'a> {
     offset: usize,
     first_byte: u8,
     decoder: AnyDecoder<R>,
+    io_error: Option<io::Error>,
     _phantom: PhantomData<&'a ()>,
 }
 
@@ -339,9 +537,15 @@ impl<'a, R: Read + Send + 'a> ReaderInput<'a, R> {
     pub fn new(reader: R) -> Self {
         let mut decoder = AnyDecoder::new(reader);
         let mut data = vec![0; DEFAULT_BUFFER_SIZE];
-        let len = decoder
-            .read(&mut data[..64])
+        let mut len = decoder
+            .read(&mut data[..CHUNK_WIDTH])
             .expect("Error while reading data");
+        // skip a leading UTF-8 BOM so that offsets are relative to the actual content
+        if data[..len].starts_with(&UTF8_BOM) {
+            data.copy_within(UTF8_BOM.len()..len, 0);
+            len -= UTF8_BOM.len();
+            data[len..len + UTF8_BOM.len()].fill(0);
+        }
         let first_byte = data[0];
         Self {
             data,
@@ -350,9 +554,25 @@ impl<'a, R: Read + Send + 'a> ReaderInput<'a, R> {
             offset: 0,
             first_byte,
             decoder,
+            io_error: None,
             _phantom: PhantomData,
         }
     }
+
+    /// The I/O or decompression error that ended the iteration, if any.
+    /// The iterator reports an error as a premature end of input; check this
+    /// after it returns `None` to tell the two apart.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.io_error.as_ref()
+    }
+}
+
+impl<'a> ReaderInput<'a, File> {
+    /// Wrap an already-opened [`File`], e.g. one opened under a different
+    /// sandbox policy or received as a raw fd.
+    pub fn from_file(file: File) -> Self {
+        Self::new(file)
+    }
 }
 
 impl<'a, R: Read + Send + 'a> Iterator for ReaderInput<'a, R> {
@@ -361,28 +581,36 @@ impl<'a, R: Read + Send + 'a> Iterator for ReaderInput<'a, R> {
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         if self.pos >= self.len {
-            let n = self
-                .decoder
-                .read(&mut self.data)
-                .expect("Error while reading data");
+            let n = match self.decoder.read(&mut self.data) {
+                Ok(n) => n,
+                Err(err) => {
+                    self.io_error = Some(err);
+                    return None;
+                }
+            };
             if n == 0 {
                 return None;
             }
             self.offset += self.len;
             self.pos = 0;
             self.len = n;
-            let padded_len = self.len.next_multiple_of(64);
+            let padded_len = self.len.next_multiple_of(CHUNK_WIDTH);
             self.data[self.len..padded_len].fill(0);
         }
         let pos = self.pos;
-        self.pos += 64;
-        if pos + 64 <= self.len {
-            unsafe { Some(std::slice::from_raw_parts(self.data.as_ptr().add(pos), 64)) }
+        self.pos += CHUNK_WIDTH;
+        if pos + CHUNK_WIDTH <= self.len {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    CHUNK_WIDTH,
+                ))
+            }
         } else {
             unsafe {
                 Some(std::slice::from_raw_parts(
                     self.data.as_ptr().add(pos),
-                    self.len % 64,
+                    self.len % CHUNK_WIDTH,
                 ))
             }
         }
@@ -394,13 +622,20 @@ impl<'a, R: Read + Send + 'a> InputData<'a> for ReaderInput<'a, R> {
 
     #[inline(always)]
     fn current_chunk(&self) -> &[u8] {
-        if 64 <= self.pos && self.pos <= self.len {
-            unsafe { std::slice::from_raw_parts(self.data.as_ptr().add(self.pos - 64), 64) }
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data.as_ptr().add(self.pos - CHUNK_WIDTH),
+                    CHUNK_WIDTH,
+                )
+            }
         } else {
             unsafe {
                 std::slice::from_raw_parts(
-                    self.data.as_ptr().add((self.len / 64) * 64),
-                    self.len % 64,
+                    self.data
+                        .as_ptr()
+                        .add((self.len / CHUNK_WIDTH) * CHUNK_WIDTH),
+                    self.len % CHUNK_WIDTH,
                 )
             }
         }
@@ -408,13 +643,23 @@ impl<'a, R: Read + Send + 'a> InputData<'a> for ReaderInput<'a, R> {
 
     #[inline(always)]
     fn current_chunk_len(&self) -> usize {
-        if 64 <= self.pos && self.pos <= self.len {
-            64
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            CHUNK_WIDTH
         } else {
-            self.len % 64
+            self.len % CHUNK_WIDTH
         }
     }
 
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.offset
+            + if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+                self.pos - CHUNK_WIDTH
+            } else {
+                (self.len / CHUNK_WIDTH) * CHUNK_WIDTH
+            }
+    }
+
     #[inline(always)]
     fn buffer(&self) -> &[u8] {
         &self.data
@@ -433,12 +678,15 @@ impl<'a, R: Read + Send + 'a> InputData<'a> for ReaderInput<'a, R> {
     #[inline(always)]
     fn grow_buffer(&mut self, additional: usize) {
         self.data.resize(self.len + additional, 0);
-        let n = self
-            .decoder
-            .read(&mut self.data[self.len..])
-            .expect("Error while reading data");
+        let n = match self.decoder.read(&mut self.data[self.len..]) {
+            Ok(n) => n,
+            Err(err) => {
+                self.io_error = Some(err);
+                0
+            }
+        };
         self.len += n;
-        let padded_len = self.len.next_multiple_of(64);
+        let padded_len = self.len.next_multiple_of(CHUNK_WIDTH);
         self.data[self.len..padded_len].fill(0);
     }
 
@@ -469,6 +717,662 @@ pub trait FromReader<'a, R: Read + Send + 'a>: FromInputData<'a, ReaderInput<'a,
 
 impl<'a, R: Read + Send + 'a, F: FromInputData<'a, ReaderInput<'a, R>>> FromReader<'a, R> for F {}
 
+/// Maximum size of a bgzf block, compressed or decompressed.
+#[cfg(feature = "gz")]
+const BGZF_MAX_BLOCK_SIZE: usize = 1 << 16;
+
+/// Bgzf input, decompressing one gzip block at a time.
+/// Unlike plain gzip, the block structure allows seeking to a
+/// [virtual offset](BgzfInput::seek_virtual) obtained from an index (e.g. `.gzi`).
+/// It does not support parallel processing.
+#[cfg(feature = "gz")]
+pub struct BgzfInput<'a, R: Read + Send + 'a> {
+    reader: R,
+    data: Vec<u8>,
+    cdata: Vec<u8>,
+    len: usize,
+    pos: usize,
+    offset: usize,
+    coffset: u64,
+    first_byte: u8,
+    _phantom: PhantomData<&'a ()>,
+}
+
+#[cfg(feature = "gz")]
+impl<'a, R: Read + Send + 'a> BgzfInput<'a, R> {
+    pub fn new(reader: R) -> Self {
+        let mut input = Self {
+            reader,
+            data: vec![0; BGZF_MAX_BLOCK_SIZE],
+            cdata: Vec::new(),
+            len: 0,
+            pos: 0,
+            offset: 0,
+            coffset: 0,
+            first_byte: 0,
+            _phantom: PhantomData,
+        };
+        input.len = input.next_block();
+        input.first_byte = input.data[0];
+        input
+    }
+
+    /// Returns the virtual offset of the current block:
+    /// the compressed offset of the next block start in the upper 48 bits.
+    pub fn virtual_offset(&self) -> u64 {
+        self.coffset << 16
+    }
+
+    /// Decompress the next non-empty block into `data`, skipping empty blocks
+    /// (including the EOF marker). Returns the decompressed size, or 0 at the
+    /// end of the input.
+    fn next_block(&mut self) -> usize {
+        loop {
+            let mut header = [0; 12];
+            if !read_or_eof(&mut self.reader, &mut header) {
+                return 0;
+            }
+            assert_eq!(
+                header[..4],
+                [0x1F, 0x8B, 0x08, 0x04],
+                "Invalid bgzf block header"
+            );
+            let xlen = u16::from_le_bytes([header[10], header[11]]) as usize;
+            self.cdata.resize(xlen, 0);
+            self.reader
+                .read_exact(&mut self.cdata)
+                .expect("Error while reading data");
+            // look for the BC subfield holding BSIZE, the total block size minus one
+            let mut bsize = None;
+            let mut i = 0;
+            while i + 4 <= xlen {
+                let slen = u16::from_le_bytes([self.cdata[i + 2], self.cdata[i + 3]]) as usize;
+                if self.cdata[i..i + 2] == *b"BC" && slen == 2 {
+                    bsize =
+                        Some(u16::from_le_bytes([self.cdata[i + 4], self.cdata[i + 5]]) as usize);
+                }
+                i += 4 + slen;
+            }
+            let bsize = bsize.expect("Missing BSIZE subfield in bgzf block");
+            let cdata_len = bsize + 1 - 12 - xlen - 8;
+            self.cdata.resize(cdata_len, 0);
+            self.reader
+                .read_exact(&mut self.cdata)
+                .expect("Error while reading data");
+            let mut footer = [0; 8];
+            self.reader
+                .read_exact(&mut footer)
+                .expect("Error while reading data");
+            let isize = u32::from_le_bytes([footer[4], footer[5], footer[6], footer[7]]) as usize;
+            self.coffset += (bsize + 1) as u64;
+            if isize == 0 {
+                continue;
+            }
+            let padded_len = isize.next_multiple_of(CHUNK_WIDTH);
+            if self.data.len() < padded_len {
+                self.data.resize(padded_len, 0);
+            }
+            let mut decoder = flate2::read::DeflateDecoder::new(self.cdata.as_slice());
+            decoder
+                .read_exact(&mut self.data[..isize])
+                .expect("Error while decompressing bgzf block");
+            self.data[isize..padded_len].fill(0);
+            return isize;
+        }
+    }
+}
+
+#[cfg(feature = "gz")]
+impl<'a, R: Read + Seek + Send + 'a> BgzfInput<'a, R> {
+    /// Seek to a bgzf virtual offset: the compressed offset of a block start
+    /// in the upper 48 bits, the offset within the decompressed block in the
+    /// lower 16 bits.
+    /// Chunk offsets restart at zero from the target position.
+    pub fn seek_virtual(&mut self, voffset: u64) {
+        let coffset = voffset >> 16;
+        let uoffset = (voffset & 0xFFFF) as usize;
+        self.reader
+            .seek(SeekFrom::Start(coffset))
+            .expect("Error while seeking");
+        self.coffset = coffset;
+        let len = self.next_block();
+        assert!(uoffset <= len, "Virtual offset beyond the end of the block");
+        self.data
+            .copy_within(uoffset..len.next_multiple_of(CHUNK_WIDTH), 0);
+        self.len = len - uoffset;
+        self.pos = 0;
+        self.offset = 0;
+    }
+}
+
+/// Fill `buf` completely, or return `false` if the input ends right before it.
+#[cfg(feature = "gz")]
+fn read_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> bool {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = reader
+            .read(&mut buf[filled..])
+            .expect("Error while reading data");
+        if n == 0 {
+            assert!(filled == 0, "Truncated bgzf block");
+            return false;
+        }
+        filled += n;
+    }
+    true
+}
+
+#[cfg(feature = "gz")]
+impl<'a, R: Read + Send + 'a> Iterator for BgzfInput<'a, R> {
+    type Item = &'a [u8];
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            let n = self.next_block();
+            if n == 0 {
+                return None;
+            }
+            self.offset += self.len;
+            self.pos = 0;
+            self.len = n;
+        }
+        let pos = self.pos;
+        self.pos += CHUNK_WIDTH;
+        if pos + CHUNK_WIDTH <= self.len {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    CHUNK_WIDTH,
+                ))
+            }
+        } else {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    self.len % CHUNK_WIDTH,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gz")]
+impl<'a, R: Read + Send + 'a> InputData<'a> for BgzfInput<'a, R> {
+    const RANDOM_ACCESS: bool = false;
+
+    #[inline(always)]
+    fn current_chunk(&self) -> &[u8] {
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data.as_ptr().add(self.pos - CHUNK_WIDTH),
+                    CHUNK_WIDTH,
+                )
+            }
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data
+                        .as_ptr()
+                        .add((self.len / CHUNK_WIDTH) * CHUNK_WIDTH),
+                    self.len % CHUNK_WIDTH,
+                )
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn current_chunk_len(&self) -> usize {
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            CHUNK_WIDTH
+        } else {
+            self.len % CHUNK_WIDTH
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.offset
+            + if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+                self.pos - CHUNK_WIDTH
+            } else {
+                (self.len / CHUNK_WIDTH) * CHUNK_WIDTH
+            }
+    }
+
+    #[inline(always)]
+    fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline(always)]
+    fn buffer_offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline(always)]
+    fn is_end_of_buffer(&self) -> bool {
+        self.pos >= self.len
+    }
+
+    #[inline(always)]
+    fn first_byte(&self) -> u8 {
+        self.first_byte
+    }
+
+    #[inline(always)]
+    fn compression_format(&mut self) -> io::Result<Option<deko::Format>> {
+        Ok(Some(deko::Format::Gz))
+    }
+}
+
+#[cfg(feature = "gz")]
+pub trait FromBgzf<'a, R: Read + Send + 'a>: FromInputData<'a, BgzfInput<'a, R>> {
+    /// Build the struct from a bgzf-compressed reader, decompressing one block at a time.
+    /// It does not support parallel processing.
+    #[inline(always)]
+    fn from_bgzf(reader: R) -> Self {
+        Self::from_input(BgzfInput::new(reader))
+    }
+}
+
+#[cfg(feature = "gz")]
+impl<'a, R: Read + Send + 'a, F: FromInputData<'a, BgzfInput<'a, R>>> FromBgzf<'a, R> for F {}
+
+/// Input backed by a [`BufRead`], serving 64-byte chunks directly from the
+/// reader's internal buffer when possible.
+/// Chunks are only copied (into `spill`) when they straddle two fills or need
+/// zero-padding at the tail, which avoids the internal copy of [`ReaderInput`]
+/// for uncompressed piped input.
+/// It does not support transparent decompression or parallel processing.
+pub struct BufReadInput<'a, R: BufRead + Send + 'a> {
+    reader: R,
+    region_ptr: *const u8,
+    region_len: usize,
+    pos: usize,
+    offset: usize,
+    chunk_start: usize,
+    spill: Box<[u8; CHUNK_WIDTH]>,
+    spill_len: usize,
+    in_spill: bool,
+    first_byte: u8,
+    io_error: Option<io::Error>,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, R: BufRead + Send + 'a> BufReadInput<'a, R> {
+    pub fn new(mut reader: R) -> Self {
+        // skip a leading UTF-8 BOM so that offsets are relative to the actual content
+        let buf = reader.fill_buf().expect("Error while reading data");
+        if buf.starts_with(&UTF8_BOM) {
+            reader.consume(UTF8_BOM.len());
+        }
+        let buf = reader.fill_buf().expect("Error while reading data");
+        assert!(!buf.is_empty());
+        let (region_ptr, region_len, first_byte) = (buf.as_ptr(), buf.len(), buf[0]);
+        Self {
+            reader,
+            region_ptr,
+            region_len,
+            pos: 0,
+            offset: 0,
+            chunk_start: 0,
+            spill: Box::new([0; CHUNK_WIDTH]),
+            spill_len: 0,
+            in_spill: false,
+            first_byte,
+            io_error: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The I/O error that ended the iteration, if any.
+    /// The iterator reports an error as a premature end of input; check this
+    /// after it returns `None` to tell the two apart.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.io_error.as_ref()
+    }
+
+    /// Mark the current fill region as consumed.
+    #[inline(always)]
+    fn consume_region(&mut self) {
+        self.reader.consume(self.region_len);
+        self.offset += self.region_len;
+        self.region_len = 0;
+        self.pos = 0;
+    }
+
+    /// Build the next chunk in `spill` from the end of the current fill region
+    /// and the following one(s), zero-padding at the end of the input.
+    #[inline(always)]
+    fn next_spill_chunk(&mut self) -> Option<&'a [u8]> {
+        let start = self.offset + self.pos;
+        let rem = self.region_len - self.pos;
+        if rem > 0 {
+            let region = unsafe { std::slice::from_raw_parts(self.region_ptr, self.region_len) };
+            self.spill[..rem].copy_from_slice(&region[self.pos..]);
+        } else if self.pos >= CHUNK_WIDTH {
+            // keep the last served chunk readable in case this is the end of the input
+            let last = unsafe {
+                std::slice::from_raw_parts(self.region_ptr.add(self.pos - CHUNK_WIDTH), CHUNK_WIDTH)
+            };
+            self.spill.copy_from_slice(last);
+        }
+        self.consume_region();
+        let mut filled = rem;
+        while filled < CHUNK_WIDTH {
+            let new = match self.reader.fill_buf() {
+                Ok(new) => new,
+                Err(err) => {
+                    // report the error as a premature end of input
+                    self.io_error = Some(err);
+                    &[]
+                }
+            };
+            if new.is_empty() {
+                if filled == 0 {
+                    // the input ended on a chunk boundary, `spill` holds the last chunk
+                    self.in_spill = true;
+                    self.spill_len = CHUNK_WIDTH;
+                    return None;
+                }
+                self.spill[filled..].fill(0);
+                self.in_spill = true;
+                self.spill_len = filled;
+                self.chunk_start = start;
+                return Some(unsafe { std::slice::from_raw_parts(self.spill.as_ptr(), filled) });
+            }
+            let take = (CHUNK_WIDTH - filled).min(new.len());
+            self.spill[filled..filled + take].copy_from_slice(&new[..take]);
+            filled += take;
+            self.region_ptr = new.as_ptr();
+            self.region_len = new.len();
+            self.pos = take;
+            if take == self.region_len {
+                self.consume_region();
+            }
+        }
+        self.in_spill = true;
+        self.spill_len = CHUNK_WIDTH;
+        self.chunk_start = start;
+        Some(unsafe { std::slice::from_raw_parts(self.spill.as_ptr(), CHUNK_WIDTH) })
+    }
+}
+
+impl<'a, R: BufRead + Send + 'a> Iterator for BufReadInput<'a, R> {
+    type Item = &'a [u8];
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos + CHUNK_WIDTH <= self.region_len {
+            let pos = self.pos;
+            self.pos += CHUNK_WIDTH;
+            self.in_spill = false;
+            self.chunk_start = self.offset + pos;
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.region_ptr.add(pos),
+                    CHUNK_WIDTH,
+                ))
+            }
+        } else {
+            self.next_spill_chunk()
+        }
+    }
+}
+
+impl<'a, R: BufRead + Send + 'a> InputData<'a> for BufReadInput<'a, R> {
+    const RANDOM_ACCESS: bool = false;
+
+    #[inline(always)]
+    fn current_chunk(&self) -> &[u8] {
+        if self.in_spill {
+            &self.spill[..self.spill_len]
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(self.region_ptr.add(self.pos - CHUNK_WIDTH), CHUNK_WIDTH)
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn current_chunk_len(&self) -> usize {
+        if self.in_spill {
+            self.spill_len
+        } else {
+            CHUNK_WIDTH
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.chunk_start
+    }
+
+    #[inline(always)]
+    fn buffer(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.region_ptr, self.region_len) }
+    }
+
+    #[inline(always)]
+    fn buffer_offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline(always)]
+    fn is_end_of_buffer(&self) -> bool {
+        self.pos >= self.region_len
+    }
+
+    #[inline(always)]
+    fn first_byte(&self) -> u8 {
+        self.first_byte
+    }
+}
+
+pub trait FromBufRead<'a, R: BufRead + Send + 'a>: FromInputData<'a, BufReadInput<'a, R>> {
+    /// Build the struct from a [`BufRead`], avoiding the internal copy of
+    /// [`from_reader`](FromReader::from_reader) for uncompressed input.
+    /// It does not support transparent decompression or parallel processing.
+    #[inline(always)]
+    fn from_buf_read(reader: R) -> Self {
+        Self::from_input(BufReadInput::new(reader))
+    }
+}
+
+impl<'a, R: BufRead + Send + 'a, F: FromInputData<'a, BufReadInput<'a, R>>> FromBufRead<'a, R>
+    for F
+{
+}
+
+/// Input stitched from an iterator of byte buffers of arbitrary sizes, e.g.
+/// multipart downloads or buffers handed over an `mpsc` channel
+/// (`Receiver::into_iter`).
+/// A buffer ending mid-window has its remainder carried over and completed
+/// from the following buffers; the final partial window is zero-padded.
+/// It does not support transparent decompression or parallel processing.
+pub struct ChunkedInput<'a, S: Iterator<Item = Vec<u8>>> {
+    source: S,
+    data: Vec<u8>,
+    len: usize,
+    pos: usize,
+    offset: usize,
+    first_byte: u8,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, S: Iterator<Item = Vec<u8>>> ChunkedInput<'a, S> {
+    pub fn new(source: S) -> Self {
+        let mut input = Self {
+            source,
+            data: Vec::new(),
+            len: 0,
+            pos: 0,
+            offset: 0,
+            first_byte: 0,
+            _phantom: PhantomData,
+        };
+        // pull the first non-empty buffer so `first_byte` is available
+        while input.len == 0 {
+            match input.source.next() {
+                Some(buf) => input.append(&buf),
+                None => break,
+            }
+        }
+        // skip a leading UTF-8 BOM so that offsets are relative to the actual content
+        if input.data[..input.len].starts_with(&UTF8_BOM) {
+            input.data.copy_within(UTF8_BOM.len()..input.len, 0);
+            input.len -= UTF8_BOM.len();
+            input.data[input.len..input.len + UTF8_BOM.len()].fill(0);
+        }
+        input.first_byte = input.data.first().copied().unwrap_or(0);
+        input
+    }
+
+    /// Append a source buffer behind the pending bytes, keeping the window
+    /// past the end zero-padded.
+    fn append(&mut self, buf: &[u8]) {
+        let new_len = self.len + buf.len();
+        let padded = new_len.next_multiple_of(CHUNK_WIDTH);
+        if self.data.len() < padded {
+            self.data.resize(padded, 0);
+        }
+        self.data[self.len..new_len].copy_from_slice(buf);
+        self.data[new_len..padded].fill(0);
+        self.len = new_len;
+    }
+}
+
+impl<'a, S: Iterator<Item = Vec<u8>>> Iterator for ChunkedInput<'a, S> {
+    type Item = &'a [u8];
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos > self.len {
+            // the final partial window was already served
+            return None;
+        }
+        while self.len - self.pos < CHUNK_WIDTH {
+            match self.source.next() {
+                Some(buf) => {
+                    // drop the served windows, carrying the remainder
+                    self.data.copy_within(self.pos..self.len, 0);
+                    self.len -= self.pos;
+                    self.offset += self.pos;
+                    self.pos = 0;
+                    self.append(&buf);
+                }
+                None => {
+                    if self.pos >= self.len {
+                        return None;
+                    }
+                    // serve the final partial window below
+                    break;
+                }
+            }
+        }
+        let pos = self.pos;
+        self.pos += CHUNK_WIDTH;
+        if pos + CHUNK_WIDTH <= self.len {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    CHUNK_WIDTH,
+                ))
+            }
+        } else {
+            unsafe {
+                Some(std::slice::from_raw_parts(
+                    self.data.as_ptr().add(pos),
+                    self.len % CHUNK_WIDTH,
+                ))
+            }
+        }
+    }
+}
+
+impl<'a, S: Iterator<Item = Vec<u8>>> InputData<'a> for ChunkedInput<'a, S> {
+    const RANDOM_ACCESS: bool = false;
+
+    #[inline(always)]
+    fn current_chunk(&self) -> &[u8] {
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data.as_ptr().add(self.pos - CHUNK_WIDTH),
+                    CHUNK_WIDTH,
+                )
+            }
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(
+                    self.data
+                        .as_ptr()
+                        .add((self.len / CHUNK_WIDTH) * CHUNK_WIDTH),
+                    self.len % CHUNK_WIDTH,
+                )
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn current_chunk_len(&self) -> usize {
+        if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+            CHUNK_WIDTH
+        } else {
+            self.len % CHUNK_WIDTH
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.offset
+            + if CHUNK_WIDTH <= self.pos && self.pos <= self.len {
+                self.pos - CHUNK_WIDTH
+            } else {
+                (self.len / CHUNK_WIDTH) * CHUNK_WIDTH
+            }
+    }
+
+    #[inline(always)]
+    fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline(always)]
+    fn buffer_offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline(always)]
+    fn is_end_of_buffer(&self) -> bool {
+        self.pos >= self.len
+    }
+
+    #[inline(always)]
+    fn first_byte(&self) -> u8 {
+        self.first_byte
+    }
+}
+
+pub trait FromChunks<'a, S: Iterator<Item = Vec<u8>>>:
+    FromInputData<'a, ChunkedInput<'a, S>>
+{
+    /// Build the struct from an iterator of byte buffers of arbitrary sizes,
+    /// stitched into the parser's fixed-size windows.
+    /// It does not support transparent decompression or parallel processing.
+    #[inline(always)]
+    fn from_chunks(source: S) -> Self {
+        Self::from_input(ChunkedInput::new(source))
+    }
+}
+
+impl<'a, S: Iterator<Item = Vec<u8>>, F: FromInputData<'a, ChunkedInput<'a, S>>> FromChunks<'a, S>
+    for F
+{
+}
+
 /// File input.
 /// It supports transparent decompression, but not parallel processing.
 pub struct FileInput {
@@ -477,9 +1381,41 @@ pub struct FileInput {
 
 impl FileInput {
     pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
-        Ok(Self {
-            reader: ReaderInput::new(File::open(path)?),
-        })
+        Ok(Self::from_file(File::open(path)?))
+    }
+
+    /// Wrap an already-opened [`File`], e.g. one opened under a different
+    /// sandbox policy or received as a raw fd.
+    pub fn from_file(file: File) -> Self {
+        Self {
+            reader: ReaderInput::new(file),
+        }
+    }
+
+    /// The I/O or decompression error that ended the iteration, if any.
+    /// The iterator reports an error as a premature end of input; check this
+    /// after it returns `None` to tell the two apart.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.reader.io_error()
+    }
+
+    /// Open a file while asserting its compression format, e.g. known from its
+    /// extension (`None` for an uncompressed file).
+    /// A corrupt or truncated header then surfaces as an error instead of
+    /// being silently parsed verbatim.
+    pub fn with_format_hint<P: AsRef<Path>>(
+        path: P,
+        hint: Option<deko::Format>,
+    ) -> io::Result<Self> {
+        let mut input = Self::new(path)?;
+        let format = input.compression_format()?;
+        if format != hint {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Compression format mismatch: expected {hint:?}, detected {format:?}"),
+            ));
+        }
+        Ok(input)
     }
 }
 
@@ -505,6 +1441,11 @@ impl InputData<'static> for FileInput {
         self.reader.current_chunk_len()
     }
 
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.reader.chunk_offset()
+    }
+
     #[inline(always)]
     fn buffer(&self) -> &[u8] {
         self.reader.buffer()
@@ -560,6 +1501,13 @@ impl StdinInput {
             reader: ReaderInput::new(stdin()),
         }
     }
+
+    /// The I/O or decompression error that ended the iteration, if any.
+    /// The iterator reports an error as a premature end of input; check this
+    /// after it returns `None` to tell the two apart.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.reader.io_error()
+    }
 }
 
 impl Iterator for StdinInput {
@@ -584,6 +1532,11 @@ impl InputData<'static> for StdinInput {
         self.reader.current_chunk_len()
     }
 
+    #[inline(always)]
+    fn chunk_offset(&self) -> usize {
+        self.reader.chunk_offset()
+    }
+
     #[inline(always)]
     fn buffer(&self) -> &[u8] {
         self.reader.buffer()
@@ -625,3 +1578,343 @@ pub trait FromStdin: FromInputData<'static, StdinInput> {
 }
 
 impl<F: FromInputData<'static, StdinInput>> FromStdin for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{FastaParser, Parser};
+    use crate::{Config, ParserOptions};
+
+    const CONFIG: Config = ParserOptions::default().config();
+
+    #[test]
+    fn test_skip_bom() {
+        static FASTA: &[u8] = b"\xEF\xBB\xBF>h\nACGT".as_slice();
+
+        let mut f = FastaParser::<CONFIG, _>::from_slice(FASTA);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"h");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+
+        let mut f = FastaParser::<CONFIG, _>::from_reader(FASTA);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"h");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+    }
+
+    #[test]
+    fn test_slice_input_short_lengths() {
+        // partial-chunk boundaries: shorter than a chunk, exact multiples,
+        // and one byte past a multiple
+        for len in [1, 63, 64, 65, 127, 128] {
+            let data: Vec<u8> = (0..len).map(|i| b"ACGT"[i % 4]).collect();
+            let mut input = SliceInput::new(&data);
+            let mut seen = 0;
+            while let Some(chunk) = input.next() {
+                assert_eq!(chunk, &data[seen..seen + chunk.len()], "length {len}");
+                // the accessors agree with the chunk just returned
+                assert_eq!(input.current_chunk(), chunk, "length {len}");
+                assert_eq!(input.current_chunk_len(), chunk.len(), "length {len}");
+                assert_eq!(input.chunk_offset(), seen, "length {len}");
+                seen += chunk.len();
+            }
+            // every byte is reported exactly once
+            assert_eq!(seen, len);
+            assert!(input.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join("helicase_test_from_file.fasta");
+        std::fs::write(&path, b">h\nACGT").unwrap();
+
+        let file = File::open(&path).unwrap();
+        let mut f = FastaParser::<CONFIG, _>::from_input(FileInput::from_file(file));
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"h");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+
+        let file = File::open(&path).unwrap();
+        let mut f = FastaParser::<CONFIG, _>::from_input(ReaderInput::from_file(file));
+        assert!(f.next().is_some());
+        assert_eq!(f.get_dna_string(), b"ACGT");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "bz2")]
+    fn test_bz2_input() {
+        // `>bzxz\nACGTACGT\nTTAA\n` compressed with bzip2
+        static BZ2: &[u8] = &[
+            0x42, 0x5a, 0x68, 0x39, 0x31, 0x41, 0x59, 0x26, 0x53, 0x59, 0x9f, 0xba, 0x38, 0xed,
+            0x00, 0x00, 0x01, 0xcf, 0x80, 0x00, 0x10, 0x00, 0x01, 0x28, 0x80, 0x04, 0x00, 0x10,
+            0x00, 0x00, 0x50, 0x20, 0x00, 0x21, 0xa3, 0x4d, 0x34, 0xd0, 0x83, 0x26, 0x20, 0xc7,
+            0x98, 0x41, 0x23, 0x43, 0x56, 0x9b, 0x93, 0xc5, 0xdc, 0x91, 0x4e, 0x14, 0x24, 0x27,
+            0xee, 0x8e, 0x3b, 0x40,
+        ];
+        let mut f = FastaParser::<CONFIG, _>::from_reader(BZ2);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"bzxz");
+        assert_eq!(f.get_dna_string(), b"ACGTACGTTTAA");
+        assert_eq!(f.compression().unwrap(), Some(deko::Format::Bz));
+    }
+
+    #[test]
+    #[cfg(feature = "xz")]
+    fn test_xz_input() {
+        // `>bzxz\nACGTACGT\nTTAA\n` compressed with xz
+        static XZ: &[u8] = &[
+            0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00, 0x00, 0x04, 0xe6, 0xd6, 0xb4, 0x46, 0x02, 0x00,
+            0x21, 0x01, 0x16, 0x00, 0x00, 0x00, 0x74, 0x2f, 0xe5, 0xa3, 0x01, 0x00, 0x13, 0x3e,
+            0x62, 0x7a, 0x78, 0x7a, 0x0a, 0x41, 0x43, 0x47, 0x54, 0x41, 0x43, 0x47, 0x54, 0x0a,
+            0x54, 0x54, 0x41, 0x41, 0x0a, 0x00, 0x9e, 0xa5, 0x3d, 0xe7, 0x71, 0x14, 0x71, 0x8b,
+            0x00, 0x01, 0x2c, 0x14, 0xf8, 0x0a, 0x6d, 0x03, 0x1f, 0xb6, 0xf3, 0x7d, 0x01, 0x00,
+            0x00, 0x00, 0x00, 0x04, 0x59, 0x5a,
+        ];
+        let mut f = FastaParser::<CONFIG, _>::from_reader(XZ);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"bzxz");
+        assert_eq!(f.get_dna_string(), b"ACGTACGTTTAA");
+        assert_eq!(f.compression().unwrap(), Some(deko::Format::Xz));
+    }
+
+    #[test]
+    fn test_mmap_checked() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join("helicase_test_mmap_checked.fasta");
+        std::fs::write(&path, b">h\nACGTACGT\n").unwrap();
+        let input = MmapInput::new_checked(&path).unwrap();
+        let mapped_len = input.len();
+        assert_eq!(mapped_len, 12);
+        assert!(input.is_complete_file().unwrap());
+
+        // growing the file invalidates the completeness check, but the
+        // mapped windows stay within the original length
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b">i\nTTTT\n").unwrap();
+        drop(file);
+        assert!(!input.is_complete_file().unwrap());
+        assert_eq!(input.len(), mapped_len);
+        let consumed: usize = input.map(|chunk| chunk.len()).sum();
+        assert_eq!(consumed, mapped_len);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "gz")]
+    fn test_format_hint() {
+        let path = std::env::temp_dir().join("helicase_test_format_hint.fasta");
+        std::fs::write(&path, b">h\nACGT").unwrap();
+
+        // plain content matches an uncompressed hint but not a gzip one
+        assert!(FileInput::with_format_hint(&path, None).is_ok());
+        match FileInput::with_format_hint(&path, Some(deko::Format::Gz)) {
+            Err(err) => assert_eq!(err.kind(), io::ErrorKind::InvalidData),
+            Ok(_) => panic!("Expected a format mismatch error"),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "gz")]
+    fn test_bgzf_input() {
+        use flate2::{Compression, write::DeflateEncoder};
+        use std::io::Write;
+
+        fn bgzf_block(data: &[u8]) -> Vec<u8> {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).unwrap();
+            let cdata = encoder.finish().unwrap();
+            let bsize = (12 + 6 + cdata.len() + 8 - 1) as u16;
+            let mut crc = flate2::Crc::new();
+            crc.update(data);
+            let mut block = vec![0x1F, 0x8B, 0x08, 0x04, 0, 0, 0, 0, 0, 0xFF];
+            block.extend_from_slice(&6u16.to_le_bytes());
+            block.extend_from_slice(b"BC");
+            block.extend_from_slice(&2u16.to_le_bytes());
+            block.extend_from_slice(&bsize.to_le_bytes());
+            block.extend_from_slice(&cdata);
+            block.extend_from_slice(&crc.sum().to_le_bytes());
+            block.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            block
+        }
+
+        static FASTA: &[u8] = b">head\nTTTCTtaAAAA\nAGAAAA\nACAA\n>hhh\nCTCTTANNAAA\nCAAAnAGCTTT\n";
+        let split = 20;
+        let mut bgzf = bgzf_block(&FASTA[..split]);
+        let block1_len = bgzf.len() as u64;
+        bgzf.extend_from_slice(&bgzf_block(&FASTA[split..]));
+        bgzf.extend_from_slice(&bgzf_block(&[])); // EOF marker
+
+        // records match the plain version of the same input
+        let mut f = FastaParser::<CONFIG, _>::from_bgzf(std::io::Cursor::new(&bgzf));
+        let mut g = FastaParser::<CONFIG, _>::from_slice(FASTA);
+        while g.next().is_some() {
+            assert!(f.next().is_some());
+            assert_eq!(f.get_header(), g.get_header());
+            assert_eq!(f.get_dna_string(), g.get_dna_string());
+        }
+        assert!(f.next().is_none());
+
+        // seek to a virtual offset inside the second block
+        let mut input = BgzfInput::new(std::io::Cursor::new(&bgzf));
+        input.seek_virtual((block1_len << 16) | 7);
+        let rest: Vec<u8> = input.flat_map(|c| c.to_vec()).collect();
+        assert_eq!(rest, &FASTA[split + 7..]);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        static FASTA: &[u8] = b">head\nACGT\n>hhh\nTTAA";
+
+        let mut f = FastaParser::<CONFIG, _>::from_vec(FASTA.to_vec());
+        assert!(f.next().is_some());
+        // random access: headers are served zero-copy from the owned buffer
+        assert_eq!(f.get_header(), b"head");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"hhh");
+        assert_eq!(f.get_dna_string(), b"TTAA");
+        assert!(f.next().is_none());
+    }
+
+    #[test]
+    fn test_buf_read_input() {
+        let data: Vec<u8> = (0..1000).map(|i| b"ACGT"[i % 4]).collect();
+        let expected: Vec<Vec<u8>> = ReaderInput::new(data.as_slice())
+            .map(|c| c.to_vec())
+            .collect();
+
+        // a slice is itself a `BufRead` serving a single fill region
+        let chunks: Vec<Vec<u8>> = BufReadInput::new(data.as_slice())
+            .map(|c| c.to_vec())
+            .collect();
+        assert_eq!(chunks, expected);
+
+        // a tiny buffer forces every chunk to straddle several fills
+        let reader = std::io::BufReader::with_capacity(10, data.as_slice());
+        let chunks: Vec<Vec<u8>> = BufReadInput::new(reader).map(|c| c.to_vec()).collect();
+        assert_eq!(chunks, expected);
+    }
+
+    #[test]
+    fn test_reader_io_error() {
+        /// A reader failing after serving its data, like a dropped connection.
+        struct FailingReader(&'static [u8]);
+
+        impl std::io::Read for FailingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::ConnectionReset, "cut off"));
+                }
+                let n = self.0.len().min(buf.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let mut input = ReaderInput::new(FailingReader(b">h\nACGT\n"));
+        while input.next().is_some() {}
+        // the error ended the iteration instead of panicking
+        assert_eq!(
+            input.io_error().map(|e| e.kind()),
+            Some(io::ErrorKind::ConnectionReset)
+        );
+    }
+
+    #[test]
+    fn test_buf_read_io_error() {
+        /// A `BufRead` erroring once its first `n` bytes are consumed.
+        struct FailingBufRead {
+            data: &'static [u8],
+            n: usize,
+        }
+
+        impl std::io::Read for FailingBufRead {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                unimplemented!()
+            }
+        }
+
+        impl BufRead for FailingBufRead {
+            fn fill_buf(&mut self) -> io::Result<&[u8]> {
+                if self.n == 0 {
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "network blip"));
+                }
+                Ok(&self.data[..self.n])
+            }
+
+            fn consume(&mut self, amt: usize) {
+                self.data = &self.data[amt..];
+                self.n -= amt;
+            }
+        }
+
+        let reader = FailingBufRead {
+            data: b">h\nACGTACGTACGTACGTACGTACGTACGT\n>t\nTTTT\n",
+            n: 20,
+        };
+        let mut input = BufReadInput::new(reader);
+        while input.next().is_some() {}
+        // the error ended the iteration instead of aborting the process
+        assert_eq!(
+            input.io_error().map(|e| e.kind()),
+            Some(io::ErrorKind::TimedOut)
+        );
+    }
+
+    #[test]
+    fn test_chunked_input() {
+        static FASTA: &[u8] =
+            b">head\nTTTCTtaAAAA\nAGAAAA\nACAA\n>hhh\nCTCTTANNAAA\nCAAAnAGCTTT\nAATTGGCC";
+
+        // buffers of sizes 10, 100, 7, ... end mid-window and mid-record
+        let buffers: Vec<Vec<u8>> = {
+            let mut buffers = Vec::new();
+            let mut rest = FASTA;
+            for size in [10, 100, 7].iter().cycle() {
+                if rest.is_empty() {
+                    break;
+                }
+                let take = (*size).min(rest.len());
+                buffers.push(rest[..take].to_vec());
+                rest = &rest[take..];
+            }
+            buffers
+        };
+
+        let mut f = FastaParser::<CONFIG, _>::from_chunks(buffers.into_iter());
+        let mut g = FastaParser::<CONFIG, _>::from_slice(FASTA);
+        while g.next().is_some() {
+            assert!(f.next().is_some());
+            assert_eq!(f.get_header(), g.get_header());
+            assert_eq!(f.get_dna_string(), g.get_dna_string());
+        }
+        assert!(f.next().is_none());
+    }
+
+    #[test]
+    fn test_from_buf_read() {
+        static FASTA: &[u8] =
+            b">head\nTTTCTtaAAAA\nAGAAAA\nACAA\n>hhh\nCTCTTANNAAA\nCAAAnAGCTTT".as_slice();
+
+        let reader = std::io::BufReader::with_capacity(7, FASTA);
+        let mut f = FastaParser::<CONFIG, _>::from_buf_read(reader);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"head");
+        assert_eq!(f.get_dna_string(), b"TTTCTtaAAAAAGAAAAACAA");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"hhh");
+        assert_eq!(f.get_dna_string(), b"CTCTTANNAAACAAAnAGCTTT");
+        assert!(f.next().is_none());
+    }
+}
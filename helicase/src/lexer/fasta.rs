@@ -9,19 +9,26 @@ use core::marker::PhantomData;
 
 pub(crate) struct FastaBitmask {
     pub open_bracket: u64,
+    pub semicolons: u64,
     pub line_feeds: u64,
     pub is_dna: u64,
+    pub gaps: u64,
     pub two_bits: u128,
     pub high_bit: u64,
     pub low_bit: u64,
 }
 
-#[derive(Default, PartialEq)]
+/// Bitmasks extracted from a 64-byte chunk of FASTA input, with one bit per byte
+/// (two for `two_bits`).
+#[derive(Clone, Default, PartialEq)]
 pub struct FastaChunk {
     pub len: usize,
     pub header: u64,
+    pub comment: u64,
     pub split: u64,
     pub is_dna: u64,
+    pub line_feeds: u64,
+    pub gaps: u64,
     pub two_bits: u128,
     pub high_bit: u64,
     pub low_bit: u64,
@@ -29,9 +36,31 @@ pub struct FastaChunk {
 
 impl Chunk for FastaChunk {}
 
+/// A lexer producing one [`FastaChunk`] of bitmasks per 64-byte chunk of input.
+///
+/// It can be used directly to consume the raw masks without going through the
+/// record-assembly state machine of [`FastaParser`](crate::parser::FastaParser):
+/// ```
+/// use helicase::input::*;
+/// use helicase::lexer::*;
+/// use helicase::{Config, ParserOptions};
+///
+/// const CONFIG: Config = ParserOptions::default().config();
+/// let mut lexer = FastaLexer::<CONFIG, _>::from_slice(b">h\nACGT");
+/// let chunk = lexer.next().unwrap();
+/// assert_eq!(chunk.len, 7);
+/// // bits beyond `len` correspond to padding and should be masked out
+/// let mask = (1 << chunk.len) - 1;
+/// assert_eq!(chunk.header & mask, 0b0000111); // `>h` and its newline
+/// assert_eq!(chunk.is_dna & mask, 0b1111000); // `ACGT`
+/// ```
+#[derive(Clone)]
 pub struct FastaLexer<'a, const CONFIG: Config, I: InputData<'a>> {
     pub(crate) input: I,
     carry: Carry,
+    comment_carry: Carry,
+    #[cfg(feature = "stats")]
+    stats: LexerStats,
     _phantom: PhantomData<&'a [u8]>,
 }
 
@@ -42,11 +71,27 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FromInputData<'a, I>
         Self {
             input,
             carry: Carry::new(false),
+            comment_carry: Carry::new(false),
+            #[cfg(feature = "stats")]
+            stats: LexerStats::default(),
             _phantom: PhantomData,
         }
     }
 }
 
+impl<'a, const CONFIG: Config, I: InputData<'a>> FastaLexer<'a, CONFIG, I> {
+    /// Replace the input and reset the carry, keeping the lexer usable for a new pass.
+    pub fn reset(&mut self, input: I) {
+        self.input = input;
+        self.carry = Carry::new(false);
+        self.comment_carry = Carry::new(false);
+        #[cfg(feature = "stats")]
+        {
+            self.stats = LexerStats::default();
+        }
+    }
+}
+
 impl<'a, const CONFIG: Config, I: InputData<'a>> Lexer for FastaLexer<'a, CONFIG, I> {
     type Input = I;
 
@@ -54,6 +99,12 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Lexer for FastaLexer<'a, CONFIG
     fn input(&self) -> &I {
         &self.input
     }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats(&self) -> LexerStats {
+        self.stats
+    }
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaLexer<'a, CONFIG, I> {
@@ -62,21 +113,36 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaLexer<'a, CON
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         self.input.next().map(|chunk| {
+            #[cfg(feature = "stats")]
+            {
+                self.stats.chunks += 1;
+                self.stats.bytes += chunk.len();
+                self.stats.tail_padding += 64 - chunk.len();
+            }
             let mask = extract_fasta_bitmask::<CONFIG>(chunk);
             let non_lf = !mask.line_feeds;
             let c = self.carry.add(mask.open_bracket, non_lf);
             let header = c ^ non_lf;
-            let is_dna = mask.is_dna & !header & non_lf;
+            let comment = if flag_is_set(CONFIG, FASTA_COMMENTS) {
+                let c = self.comment_carry.add(mask.semicolons, non_lf);
+                (c ^ non_lf) & !header
+            } else {
+                0
+            };
+            let is_dna = mask.is_dna & !header & !comment & non_lf;
             let split = if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-                !header & !is_dna & non_lf
+                !header & !comment & !is_dna & non_lf
             } else {
                 0
             };
             FastaChunk {
                 len: chunk.len(),
                 header,
+                comment,
                 split,
                 is_dna,
+                line_feeds: mask.line_feeds,
+                gaps: mask.gaps,
                 two_bits: mask.two_bits,
                 high_bit: mask.high_bit,
                 low_bit: mask.low_bit,
@@ -121,6 +187,22 @@ mod tests {
         format!("{f}")
     }
 
+    #[test]
+    #[cfg(feature = "stats")]
+    fn test_lexer_stats() {
+        let buf = vec![b'A'; 130];
+        let mut lexer = FastaLexer::<CONFIG_COLUMNAR, _>::from_slice(buf.as_slice());
+        while lexer.next().is_some() {}
+        assert_eq!(
+            lexer.stats(),
+            LexerStats {
+                chunks: 3,
+                bytes: 130,
+                tail_padding: 62,
+            }
+        );
+    }
+
     #[test]
     fn test_parse() {
         let fasta = ">head\nTTTCTtaAAAA\nAGAAAA\nACAA\n>hhh\nCTCTTANNAAA\nCAAAnAGCTTT";
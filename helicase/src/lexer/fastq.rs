@@ -3,21 +3,26 @@ use crate::config::*;
 use crate::input::*;
 use crate::simd::extract_fastq_bitmask;
 
+use core::fmt;
 use core::marker::PhantomData;
 
 pub(crate) struct FastqBitmask {
     pub line_feeds: u64,
     pub is_dna: u64,
+    pub gaps: u64,
     pub two_bits: u128,
     pub high_bit: u64,
     pub low_bit: u64,
 }
 
-#[derive(Default)]
+/// Bitmasks extracted from a 64-byte chunk of FASTQ input, with one bit per byte
+/// (two for `two_bits`).
+#[derive(Clone, Default)]
 pub struct FastqChunk {
     pub len: usize,
     pub newline: u64,
     pub is_dna: u64,
+    pub gaps: u64,
     pub two_bits: u128,
     pub high_bit: u64,
     pub low_bit: u64,
@@ -25,8 +30,15 @@ pub struct FastqChunk {
 
 impl Chunk for FastqChunk {}
 
+/// A lexer producing one [`FastqChunk`] of bitmasks per 64-byte chunk of input.
+///
+/// Like [`FastaLexer`], it can be used directly to consume the raw masks without
+/// going through the line-oriented state machine of [`FastqParser`](crate::parser::FastqParser).
+#[derive(Clone)]
 pub struct FastqLexer<'a, const CONFIG: Config, I: InputData<'a>> {
     pub(crate) input: I,
+    #[cfg(feature = "stats")]
+    stats: LexerStats,
     _phantom: PhantomData<&'a [u8]>,
 }
 
@@ -36,11 +48,24 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FromInputData<'a, I>
     fn from_input(input: I) -> Self {
         Self {
             input,
+            #[cfg(feature = "stats")]
+            stats: LexerStats::default(),
             _phantom: PhantomData,
         }
     }
 }
 
+impl<'a, const CONFIG: Config, I: InputData<'a>> FastqLexer<'a, CONFIG, I> {
+    /// Replace the input, keeping the lexer usable for a new pass.
+    pub fn reset(&mut self, input: I) {
+        self.input = input;
+        #[cfg(feature = "stats")]
+        {
+            self.stats = LexerStats::default();
+        }
+    }
+}
+
 impl<'a, const CONFIG: Config, I: InputData<'a>> Lexer for FastqLexer<'a, CONFIG, I> {
     type Input = I;
 
@@ -48,6 +73,12 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Lexer for FastqLexer<'a, CONFIG
     fn input(&self) -> &I {
         &self.input
     }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn stats(&self) -> LexerStats {
+        self.stats
+    }
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqLexer<'a, CONFIG, I> {
@@ -56,11 +87,18 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqLexer<'a, CON
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         self.input.next().map(|chunk| {
+            #[cfg(feature = "stats")]
+            {
+                self.stats.chunks += 1;
+                self.stats.bytes += chunk.len();
+                self.stats.tail_padding += 64 - chunk.len();
+            }
             let mask = extract_fastq_bitmask::<CONFIG>(chunk);
             FastqChunk {
                 len: chunk.len(),
                 newline: mask.line_feeds,
                 is_dna: mask.is_dna & !mask.line_feeds,
+                gaps: mask.gaps,
                 two_bits: mask.two_bits,
                 high_bit: mask.high_bit,
                 low_bit: mask.low_bit,
@@ -68,3 +106,38 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqLexer<'a, CON
         })
     }
 }
+
+impl fmt::Display for FastqChunk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.len {
+            let hi: bool = (self.high_bit >> i) & 1 != 0;
+            let lo: bool = (self.low_bit >> i) & 1 != 0;
+            let newline: bool = (self.newline >> i) & 1 != 0;
+            let is_dna: bool = (self.is_dna >> i) & 1 != 0;
+            match (hi, lo, newline, is_dna) {
+                (_, _, true, _) => write!(f, "\u{b6}"),
+                (_, _, _, false) => write!(f, "."),
+                (false, false, _, _) => write!(f, "A"),
+                (true, false, _, _) => write!(f, "T"),
+                (false, true, _, _) => write!(f, "C"),
+                (true, true, _, _) => write!(f, "G"),
+            }?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const CONFIG_COLUMNAR: Config = ParserOptions::default().dna_columnar().config();
+
+    #[test]
+    fn test_display() {
+        let fastq = "@h\nACGT\n+\nIIII\n";
+        let chunk = FastqLexer::<CONFIG_COLUMNAR, _>::from_slice(fastq.as_bytes())
+            .next()
+            .unwrap();
+        assert_eq!(format!("{chunk}"), "..\u{b6}ACGT\u{b6}.\u{b6}....\u{b6}");
+    }
+}
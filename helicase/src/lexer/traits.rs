@@ -1,7 +1,24 @@
 pub trait Chunk: Default {}
 
+/// Counters accumulated by a lexer while chunking its input, compiled in with
+/// the `stats` feature to avoid any overhead otherwise.
+#[cfg(feature = "stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LexerStats {
+    /// Number of 64-byte chunks produced.
+    pub chunks: usize,
+    /// Number of input bytes covered by those chunks.
+    pub bytes: usize,
+    /// Number of zero-padding bytes added to final partial chunks.
+    pub tail_padding: usize,
+}
+
 pub trait Lexer {
     type Input;
 
     fn input(&self) -> &Self::Input;
+
+    /// The [`LexerStats`] accumulated so far.
+    #[cfg(feature = "stats")]
+    fn stats(&self) -> LexerStats;
 }
@@ -1,28 +1,99 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
 mod carrying_add;
 pub mod config;
 pub mod dna_format;
+#[cfg(feature = "std")]
 pub mod input;
+#[cfg(feature = "std")]
 pub mod lexer;
+#[cfg(feature = "std")]
+pub mod output;
+#[cfg(feature = "std")]
 pub mod parser;
+#[cfg(feature = "tokio")]
+pub mod stream;
 
-pub use config::{Config, ParserOptions};
+pub use config::{Config, Events, ParserOptions};
+#[cfg(feature = "std")]
 pub use parser::{Event, FastaParser, FastqParser, FastxParser, Parser};
 
+#[cfg(feature = "std")]
 #[cfg(target_feature = "avx2")]
 pub(crate) mod simd {
     mod avx2;
     pub use avx2::*;
 }
+#[cfg(feature = "std")]
 #[cfg(target_feature = "neon")]
 pub(crate) mod simd {
     mod neon;
     pub use neon::*;
 }
-#[cfg(not(any(target_feature = "avx2", target_feature = "neon")))]
-#[deprecated(
-    note = "This parser uses AVX2 or NEON SIMD instructions. Compile using `-C target-cpu=native` to get the expected performance."
+#[cfg(feature = "std")]
+#[cfg(target_feature = "simd128")]
+pub(crate) mod simd {
+    mod wasm;
+    pub use wasm::*;
+}
+#[cfg(feature = "std")]
+#[cfg(not(any(
+    target_feature = "avx2",
+    target_feature = "neon",
+    target_feature = "simd128"
+)))]
+#[cfg_attr(
+    not(feature = "portable-fallback"),
+    deprecated(
+        note = "This parser uses AVX2, NEON or WASM SIMD128 instructions. Compile using `-C target-cpu=native` (or `-C target-feature=+simd128` on wasm32) to get the expected performance."
+    )
 )]
 pub(crate) mod simd {
     mod fallback;
     pub use fallback::*;
 }
+
+/// The chunk width of the whole pipeline, in bytes.
+///
+/// The width is currently fixed: every stage assumes one `u64` mask bit per
+/// byte (`u128` for the 2-bit packed pairs), the AVX2 backend stitches two
+/// 256-bit loads per chunk with `movemask_64`, and the inputs zero-pad their
+/// last chunk to this size.
+/// Making it a const generic `W` (e.g. 32 to spare SSE/NEON-only targets the
+/// mask stitching) would need a mask-type abstraction over `u32`/`u64`
+/// threaded through [`InputData`](input::InputData), the chunk structs, the
+/// lexers and the parsers' `pos_in_block` arithmetic — a planned
+/// generalization, recorded here so the magic number has one home; new code
+/// should use this constant instead of a literal `64`.
+pub const CHUNK_WIDTH: usize = 64;
+
+/// The name of the SIMD backend compiled in: `"avx2"`, `"neon"`, `"wasm"`,
+/// or `"scalar"` for the fallback.
+/// The backend is chosen at compile time, so applications can log a warning
+/// at startup when a binary ends up on the slow scalar path.
+pub const fn simd_backend() -> &'static str {
+    if cfg!(target_feature = "avx2") {
+        "avx2"
+    } else if cfg!(target_feature = "neon") {
+        "neon"
+    } else if cfg!(target_feature = "simd128") {
+        "wasm"
+    } else {
+        "scalar"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_simd_backend() {
+        let backend = super::simd_backend();
+        assert!(["avx2", "neon", "wasm", "scalar"].contains(&backend));
+        assert_eq!(backend == "avx2", cfg!(target_feature = "avx2"));
+        assert_eq!(backend == "neon", cfg!(target_feature = "neon"));
+        assert_eq!(backend == "wasm", cfg!(target_feature = "simd128"));
+    }
+}
@@ -0,0 +1,310 @@
+//! Output formats and helpers.
+
+use crate::parser::{Event, ParserIter};
+use deko::write::{AnyEncoder, Compression};
+use std::io::{self, Read, Write};
+
+/// A writer for the [FASTA format](https://en.wikipedia.org/wiki/FASTA_format).
+pub struct FastaWriter<W: Write> {
+    writer: W,
+    wrap: usize,
+}
+
+impl<W: Write> FastaWriter<W> {
+    /// Create a writer emitting each sequence on a single line.
+    pub fn new(writer: W) -> Self {
+        Self { writer, wrap: 0 }
+    }
+
+    /// Create a writer wrapping sequence lines at `wrap` bases.
+    pub fn with_wrap(writer: W, wrap: usize) -> Self {
+        Self { writer, wrap }
+    }
+
+    /// Write a single record.
+    pub fn write_record(&mut self, header: &[u8], seq: &[u8]) -> io::Result<()> {
+        self.writer.write_all(b">")?;
+        self.writer.write_all(header)?;
+        self.writer.write_all(b"\n")?;
+        if self.wrap == 0 {
+            self.writer.write_all(seq)?;
+            self.writer.write_all(b"\n")?;
+        } else {
+            for line in seq.chunks(self.wrap) {
+                self.writer.write_all(line)?;
+                self.writer.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A writer for the [FASTQ format](https://en.wikipedia.org/wiki/FASTQ_format).
+pub struct FastqWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FastqWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write a single record.
+    pub fn write_record(&mut self, header: &[u8], seq: &[u8], quality: &[u8]) -> io::Result<()> {
+        self.writer.write_all(b"@")?;
+        self.writer.write_all(header)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.write_all(seq)?;
+        self.writer.write_all(b"\n+\n")?;
+        self.writer.write_all(quality)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A pull-based adapter emitting the records of a [`Parser`](crate::parser::Parser)
+/// through [`Read`], the inverse of [`ReaderInput`](crate::input::ReaderInput).
+/// This makes it easy to pipe a filtered stream into another process or an
+/// [`io::copy`]; records larger than the read buffer are emitted across
+/// several `read` calls.
+pub struct FastaReadAdapter<P: ParserIter> {
+    parser: P,
+    pending: Vec<u8>,
+    cursor: usize,
+    wrap: usize,
+}
+
+impl<P: ParserIter> FastaReadAdapter<P> {
+    /// Create an adapter emitting each sequence on a single line.
+    pub fn new(parser: P) -> Self {
+        Self::with_wrap(parser, 0)
+    }
+
+    /// Create an adapter wrapping sequence lines at `wrap` bases.
+    pub fn with_wrap(parser: P, wrap: usize) -> Self {
+        Self {
+            parser,
+            pending: Vec::new(),
+            cursor: 0,
+            wrap,
+        }
+    }
+
+    /// Return the underlying parser.
+    pub fn into_inner(self) -> P {
+        self.parser
+    }
+}
+
+impl<P: ParserIter> Read for FastaReadAdapter<P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.cursor >= self.pending.len() {
+            match self.parser.next() {
+                Some(Event::Record(_)) => {
+                    self.pending.clear();
+                    self.cursor = 0;
+                    let mut writer = FastaWriter::with_wrap(&mut self.pending, self.wrap);
+                    writer.write_record(self.parser.get_header(), self.parser.get_dna_string())?;
+                }
+                Some(_) => continue,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.pending.len() - self.cursor);
+        buf[..n].copy_from_slice(&self.pending[self.cursor..self.cursor + n]);
+        self.cursor += n;
+        Ok(n)
+    }
+}
+
+/// The [FASTQ](https://en.wikipedia.org/wiki/FASTQ_format) counterpart of
+/// [`FastaReadAdapter`]: the parser must be configured with
+/// [`compute_quality`](crate::ParserOptions::compute_quality).
+pub struct FastqReadAdapter<P: ParserIter> {
+    parser: P,
+    pending: Vec<u8>,
+    cursor: usize,
+}
+
+impl<P: ParserIter> FastqReadAdapter<P> {
+    pub fn new(parser: P) -> Self {
+        Self {
+            parser,
+            pending: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Return the underlying parser.
+    pub fn into_inner(self) -> P {
+        self.parser
+    }
+}
+
+impl<P: ParserIter> Read for FastqReadAdapter<P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.cursor >= self.pending.len() {
+            match self.parser.next() {
+                Some(Event::Record(_)) => {
+                    self.pending.clear();
+                    self.cursor = 0;
+                    let quality = self
+                        .parser
+                        .get_quality()
+                        .expect("Quality was not computed, please enable COMPUTE_QUALITY");
+                    let mut writer = FastqWriter::new(&mut self.pending);
+                    writer.write_record(
+                        self.parser.get_header(),
+                        self.parser.get_dna_string(),
+                        quality,
+                    )?;
+                }
+                Some(_) => continue,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.pending.len() - self.cursor);
+        buf[..n].copy_from_slice(&self.pending[self.cursor..self.cursor + n]);
+        self.cursor += n;
+        Ok(n)
+    }
+}
+
+/// A writer compressing its output to the given [`deko::Format`],
+/// mirroring the transparent decompression of reader-based inputs.
+pub struct CompressedWriter<W: Write> {
+    encoder: AnyEncoder<W>,
+}
+
+impl<W: Write> CompressedWriter<W> {
+    pub fn new(writer: W, format: deko::Format) -> io::Result<Self> {
+        Ok(Self {
+            encoder: AnyEncoder::new(writer, format, Compression::Default)?,
+        })
+    }
+
+    /// Finish the compressed stream and return the underlying writer.
+    /// This must be called to produce a valid output.
+    pub fn finish(self) -> io::Result<W> {
+        self.encoder.finish()
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoder.write(buf)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::*;
+    use crate::parser::{FastaParser, FastqParser, Parser};
+    use crate::{Config, ParserOptions};
+
+    const CONFIG: Config = ParserOptions::default().compute_quality().config();
+
+    #[test]
+    fn test_fastq_to_fasta() {
+        // no COMPUTE_QUALITY: converting to FASTA never touches the quality
+        const CONFIG_NO_QUALITY: Config = ParserOptions::default().config();
+        let fastq = b"@r1\nACGT\n+\n!!!!\n@r2 desc\nTTTTAAAA\n+\nIIIIIIII";
+        let mut f = FastqParser::<CONFIG_NO_QUALITY, _>::from_slice(fastq);
+        let mut out = Vec::new();
+        let mut wrapped = Vec::new();
+        while f.next().is_some() {
+            f.write_as_fasta(&mut out, 0).unwrap();
+            f.write_as_fasta(&mut wrapped, 4).unwrap();
+        }
+        assert_eq!(out, b">r1\nACGT\n>r2 desc\nTTTTAAAA\n");
+        assert_eq!(wrapped, b">r1\nACGT\n>r2 desc\nTTTT\nAAAA\n");
+    }
+
+    #[test]
+    fn test_fasta_read_adapter() {
+        static FASTA: &[u8] = b">head\nTTTCTtaAAAA\nAGAAAA\nACAA\n>hhh\nCTCTTANNAAA\nCAAAnAGCTTT";
+        const CONFIG_NO_QUALITY: Config = ParserOptions::default().config();
+
+        let mut expected = FastaWriter::with_wrap(Vec::new(), 5);
+        let mut f = FastaParser::<CONFIG_NO_QUALITY, _>::from_slice(FASTA);
+        while f.next().is_some() {
+            expected
+                .write_record(f.get_header(), f.get_dna_string())
+                .unwrap();
+        }
+        let expected = expected.into_inner().unwrap();
+
+        let f = FastaParser::<CONFIG_NO_QUALITY, _>::from_slice(FASTA);
+        let mut adapter = FastaReadAdapter::with_wrap(f, 5);
+        let mut out = Vec::new();
+        io::copy(&mut adapter, &mut out).unwrap();
+        assert_eq!(out, expected);
+
+        // a tiny read buffer exercises the partial-emit cursor
+        let f = FastaParser::<CONFIG_NO_QUALITY, _>::from_slice(FASTA);
+        let mut adapter = FastaReadAdapter::with_wrap(f, 5);
+        let mut out = Vec::new();
+        let mut buf = [0; 3];
+        loop {
+            let n = adapter.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_fastq_read_adapter() {
+        let fastq = b"@r1\nACGT\n+\n!!!!\n@r2 desc\nTTTTAAAA\n+\nIIIIIIII";
+        let f = FastqParser::<CONFIG, _>::from_slice(fastq);
+        let mut adapter = FastqReadAdapter::new(f);
+        let mut out = Vec::new();
+        io::copy(&mut adapter, &mut out).unwrap();
+        assert_eq!(
+            out,
+            b"@r1\nACGT\n+\n!!!!\n@r2 desc\nTTTTAAAA\n+\nIIIIIIII\n"
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compressed_round_trip() {
+        let mut writer =
+            FastqWriter::new(CompressedWriter::new(Vec::new(), deko::Format::Zstd).unwrap());
+        writer.write_record(b"r1", b"ACGT", b"!!!!").unwrap();
+        writer.write_record(b"r2", b"TTTT", b"IIII").unwrap();
+        let compressed = writer.into_inner().unwrap().finish().unwrap();
+
+        let mut f = FastqParser::<CONFIG, _>::from_reader(compressed.as_slice());
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"r1");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+        assert_eq!(f.get_quality(), Some(b"!!!!".as_slice()));
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"r2");
+        assert_eq!(f.get_dna_string(), b"TTTT");
+        assert_eq!(f.get_quality(), Some(b"IIII".as_slice()));
+        assert!(f.next().is_none());
+    }
+}
@@ -7,6 +7,7 @@ use crate::lexer::*;
 use core::mem::swap;
 use core::ops::Range;
 
+#[derive(Clone)]
 enum State {
     Start,
     Restart,
@@ -17,21 +18,41 @@ enum State {
 }
 
 /// A parser for the [FASTA format](https://en.wikipedia.org/wiki/FASTA_format).
+///
+/// With a cloneable input (e.g. [`SliceInput`]), the parser itself is `Clone`:
+/// a clone can be advanced for speculative lookahead while the original stays
+/// on the current record.
+#[derive(Clone)]
 pub struct FastaParser<'a, const CONFIG: Config, I: InputData<'a>> {
     lexer: FastaLexer<'a, CONFIG, I>,
     finished: bool,
     state: State,
     block: FastaChunk,
-    block_counter: usize,
     pos_in_block: usize,
     header_range: Range<usize>,
     dna_range: Range<usize>,
     contiguous_dna: bool,
+    seen_dna: bool,
+    record_start: usize,
     cur_header: Vec<u8>,
     cur_dna_string: Vec<u8>,
     cur_dna_columnar: ColumnarDNA,
     cur_dna_packed: PackedDNA,
     dna_len: usize,
+    actg_len: usize,
+    base_counts: [usize; 4],
+    kmer_val: u64,
+    kmer_filled: usize,
+    kmer_queue: std::collections::VecDeque<u64>,
+    cur_gap_mask: Vec<u64>,
+    gap_mask_len: usize,
+    record_line_width: Option<usize>,
+    prev_line_len: Option<usize>,
+    line_uneven: bool,
+    seq_line_count: usize,
+    seq_hash: u64,
+    cur_line_len: usize,
+    consumed_lines: usize,
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
@@ -49,18 +70,160 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
             finished,
             state: State::Start,
             block: first,
-            block_counter: 0,
             pos_in_block: 0,
             header_range: 0..0,
             dna_range: 0..0,
             contiguous_dna: true,
+            seen_dna: false,
+            record_start: 0,
             cur_header: Vec::new(),
             cur_dna_string: Vec::new(),
             cur_dna_columnar: ColumnarDNA::new(),
             cur_dna_packed: PackedDNA::new(),
             dna_len: 0,
+            actg_len: 0,
+            seq_hash: 0,
+            base_counts: [0; 4],
+            kmer_val: 0,
+            kmer_filled: 0,
+            kmer_queue: std::collections::VecDeque::new(),
+            cur_gap_mask: Vec::new(),
+            gap_mask_len: 0,
+            record_line_width: None,
+            prev_line_len: None,
+            line_uneven: false,
+            seq_line_count: 0,
+            cur_line_len: 0,
+            consumed_lines: 0,
         }
     }
+
+    /// Reset the parser on a new input, reusing the existing allocations.
+    pub fn reset(&mut self, input: I) {
+        self.lexer.reset(input);
+        self.finished = false;
+        self.block = match self.lexer.next() {
+            Some(c) => c,
+            None => {
+                self.finished = true;
+                FastaChunk::default()
+            }
+        };
+        self.state = State::Start;
+        self.pos_in_block = 0;
+        self.header_range = 0..0;
+        self.dna_range = 0..0;
+        self.contiguous_dna = true;
+        self.seen_dna = false;
+        self.record_start = 0;
+        self.cur_header.clear();
+        self.cur_dna_string.clear();
+        self.cur_dna_columnar.clear();
+        self.cur_dna_packed.clear();
+        self.dna_len = 0;
+        self.actg_len = 0;
+        self.seq_hash = 0;
+        self.base_counts = [0; 4];
+        self.kmer_val = 0;
+        self.kmer_filled = 0;
+        self.kmer_queue.clear();
+        self.cur_gap_mask.clear();
+        self.gap_mask_len = 0;
+        self.record_line_width = None;
+        self.prev_line_len = None;
+        self.line_uneven = false;
+        self.seq_line_count = 0;
+        self.cur_line_len = 0;
+        self.consumed_lines = 0;
+    }
+
+    /// The number of sequence lines of the current record, e.g. to
+    /// reproduce the original wrapping together with
+    /// [`line_width`](Parser::line_width).
+    /// This requires [`COMPUTE_LINE_WIDTH`](crate::config::advanced::COMPUTE_LINE_WIDTH).
+    #[inline(always)]
+    pub fn sequence_line_count(&self) -> usize {
+        assert!(flag_is_set(CONFIG, COMPUTE_LINE_WIDTH));
+        self.seq_line_count
+    }
+
+    /// Get a reference to the underlying input, e.g. to check
+    /// [`buffer_offset`](InputData::buffer_offset) or
+    /// [`is_end_of_buffer`](InputData::is_end_of_buffer) mid-iteration.
+    #[inline(always)]
+    pub fn input(&self) -> &I {
+        &self.lexer.input
+    }
+
+    /// Consume the parser and reclaim the underlying input, e.g. to read
+    /// trailing metadata after the iterator finishes.
+    #[inline(always)]
+    pub fn into_input(self) -> I {
+        self.lexer.input
+    }
+
+    /// Consume the parser and compute [`FastxStats`] in a single pass over the chunks.
+    /// This requires the [`SPLIT_NON_ACTG`] and [`COMPUTE_DNA_COLUMNAR`] flags,
+    /// e.g. via [`dna_columnar`](ParserOptions#method.dna_columnar).
+    pub fn stats(mut self) -> FastxStats {
+        assert!(flag_is_set(CONFIG, SPLIT_NON_ACTG));
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR));
+        let mut stats = FastxStats::default();
+        if self.finished {
+            return stats;
+        }
+        let mut prev_header = false;
+        loop {
+            let block = &self.block;
+            let mask = if block.len == 64 {
+                !0
+            } else {
+                (1 << block.len) - 1
+            };
+            let line_feeds = block.line_feeds & mask;
+            let starts = block.header & !((block.header << 1) | prev_header as u64);
+            prev_header = (block.header >> 63) & 1 != 0;
+            stats.records += starts.count_ones() as usize;
+            stats.total_bases += ((block.is_dna | block.split) & mask).count_ones() as usize;
+            stats.n_bases += (block.split & mask).count_ones() as usize;
+            stats.gc_bases += (block.low_bit & block.is_dna & mask).count_ones() as usize;
+            stats.lines += line_feeds.count_ones() as usize;
+            match self.lexer.next() {
+                Some(b) => self.block = b,
+                None => return stats,
+            }
+        }
+    }
+
+    /// The raw 2-bit packed bases of the current 64-byte chunk and its valid
+    /// length in bytes, e.g. to feed a custom back-end with the SIMD
+    /// front-end's output and skip the [`PackedDNA`] append bookkeeping.
+    /// Byte `i` of the chunk occupies bits `2 * i..2 * i + 2`; the pairs of
+    /// non-base bytes (headers, newlines) are meaningless and must be
+    /// filtered with the chunk masks.
+    /// The packing requires the [`COMPUTE_DNA_PACKED`] flag (or k-mer
+    /// emission), which makes the front-end compute `two_bits`.
+    /// ```
+    /// use helicase::input::*;
+    /// use helicase::*;
+    ///
+    /// const CONFIG: Config = ParserOptions::default()
+    ///     .ignore_headers()
+    ///     .dna_packed()
+    ///     .config();
+    /// let mut parser = FastaParser::<CONFIG, _>::from_slice(b">s\nACGT");
+    /// let _ = parser.next();
+    /// let (two_bits, len) = parser.current_two_bits();
+    /// assert_eq!(len, 7);
+    /// // bytes 3..7 hold the bases; their 2-bit codes are (ascii >> 1) & 0b11
+    /// let bases: Vec<u8> = (3..7).map(|i| ((two_bits >> (2 * i)) & 0b11) as u8).collect();
+    /// assert_eq!(bases, [0b00, 0b01, 0b11, 0b10]); // A C G T
+    /// ```
+    #[inline(always)]
+    pub fn current_two_bits(&self) -> (u128, usize) {
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK));
+        (self.block.two_bits, self.block.len)
+    }
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FromInputData<'a, I>
@@ -77,11 +240,24 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastaParser<'a, CONF
         Format::Fasta
     }
 
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn lexer_stats(&self) -> crate::lexer::LexerStats {
+        self.lexer.stats()
+    }
+
     #[inline(always)]
     fn clear_record(&mut self) {
         if flag_is_set(CONFIG, COMPUTE_HEADER) {
             self.cur_header.clear();
         }
+        if flag_is_set(CONFIG, COMPUTE_LINE_WIDTH) {
+            self.record_line_width = None;
+            self.prev_line_len = None;
+            self.line_uneven = false;
+            self.seq_line_count = 0;
+            self.cur_line_len = 0;
+        }
         self.clear_chunk();
     }
 
@@ -89,6 +265,8 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastaParser<'a, CONF
     fn clear_chunk(&mut self) {
         if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
             self.cur_dna_string.clear();
+            // so that an empty record does not see the previous zero-copy range
+            self.dna_range = 0..0;
         }
         if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
             self.cur_dna_columnar.clear();
@@ -98,6 +276,17 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastaParser<'a, CONF
         }
         if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
             self.dna_len = 0;
+            self.actg_len = 0;
+        }
+        if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+            self.seq_hash = 0;
+        }
+        if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+            self.base_counts = [0; 4];
+        }
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            self.cur_gap_mask.clear();
+            self.gap_mask_len = 0;
         }
     }
 
@@ -181,12 +370,112 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastaParser<'a, CONF
         assert!(flag_is_set(CONFIG, COMPUTE_DNA_LEN));
         self.dna_len
     }
+
+    #[inline(always)]
+    fn get_actg_len(&self) -> usize {
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_LEN));
+        assert!(flag_is_set(CONFIG, SPLIT_NON_ACTG));
+        self.actg_len
+    }
+
+    #[inline(always)]
+    fn get_seq_hash(&self) -> u64 {
+        assert!(flag_is_set(CONFIG, COMPUTE_SEQ_HASH));
+        self.seq_hash
+    }
+
+    #[inline(always)]
+    fn compression(&mut self) -> std::io::Result<Option<deko::Format>> {
+        self.lexer.input.compression_format()
+    }
+
+    #[inline(always)]
+    fn get_gap_mask(&self) -> &[u64] {
+        assert!(flag_is_set(CONFIG, COMPUTE_GAP_MASK));
+        &self.cur_gap_mask
+    }
+
+    #[inline(always)]
+    fn try_get_header(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_HEADER) {
+            Some(self.get_header())
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_dna_string(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
+            Some(self.get_dna_string())
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_quality(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+            self.get_quality()
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn current_line(&self) -> usize {
+        let consumed = if self.pos_in_block >= 64 {
+            !0
+        } else {
+            (1 << self.pos_in_block) - 1
+        };
+        self.consumed_lines + (self.block.line_feeds & consumed).count_ones() as usize + 1
+    }
+
+    #[inline(always)]
+    fn get_base_counts(&self) -> [usize; 4] {
+        assert!(flag_is_set(CONFIG, COMPUTE_BASE_COUNTS));
+        self.base_counts
+    }
+
+    #[inline(always)]
+    fn record_bytes(&self) -> Option<&[u8]> {
+        if I::RANDOM_ACCESS {
+            Some(&self.lexer.input.data()[self.record_start..self.global_pos()])
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn line_width(&self) -> Option<usize> {
+        assert!(flag_is_set(CONFIG, COMPUTE_LINE_WIDTH));
+        if self.line_uneven {
+            return None;
+        }
+        // single-line records never validated a previous line
+        self.record_line_width.or(self.prev_line_len)
+    }
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
+    /// Offset of the current byte from the start of the (decompressed) input.
+    #[inline(always)]
+    fn global_pos(&self) -> usize {
+        self.lexer.input.chunk_offset() + self.pos_in_block
+    }
+
+    /// Fetch the next chunk, counting the newlines of the chunk being
+    /// retired for [`current_line`](Parser::current_line).
     #[inline(always)]
-    const fn global_pos(&self) -> usize {
-        64 * self.block_counter + self.pos_in_block
+    fn next_block(&mut self) -> Option<FastaChunk> {
+        let len_mask = if self.block.len == 64 {
+            !0
+        } else {
+            (1 << self.block.len) - 1
+        };
+        self.consumed_lines += (self.block.line_feeds & len_mask).count_ones() as usize;
+        self.lexer.next()
     }
 
     #[inline(always)]
@@ -194,13 +483,12 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
         let mask = !0 << self.pos_in_block;
         let mut position = self.block.header & mask;
         while position == 0 {
-            self.block = match self.lexer.next() {
+            self.block = match self.next_block() {
                 Some(b) => b,
                 None => {
                     return true;
                 }
             };
-            self.block_counter += 1;
             self.pos_in_block = 0;
             position = self.block.header;
         }
@@ -210,50 +498,111 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
 
     #[inline(always)]
     fn skip_to_header_or_dna(&mut self) -> bool {
+        // mask out the zero padding of a final partial chunk, which would
+        // otherwise be mistaken for DNA
+        let mut len_mask = if self.block.len == 64 {
+            !0
+        } else {
+            (1 << self.block.len) - 1
+        };
         let mask = !0 << self.pos_in_block;
-        let mut position = (self.block.is_dna | self.block.header) & mask;
+        let mut first_pos = self.pos_in_block;
+        let mut position = (self.block.is_dna | self.block.header) & len_mask & mask;
         while position == 0 {
-            self.block = match self.lexer.next() {
+            if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
+                // skipped non-ACTG bases still count toward the raw length
+                self.dna_len += ((self.block.split & len_mask) >> first_pos).count_ones() as usize;
+            }
+            self.block = match self.next_block() {
                 Some(b) => b,
                 None => {
                     return true;
                 }
             };
-            self.block_counter += 1;
             self.pos_in_block = 0;
-            position = self.block.is_dna | self.block.header;
+            first_pos = 0;
+            len_mask = if self.block.len == 64 {
+                !0
+            } else {
+                (1 << self.block.len) - 1
+            };
+            position = (self.block.is_dna | self.block.header) & len_mask;
         }
         self.pos_in_block = position.trailing_zeros() as usize;
+        if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
+            let below = (1u64 << self.pos_in_block) - 1;
+            self.dna_len += ((self.block.split & below) >> first_pos).count_ones() as usize;
+        }
         false
     }
 
     #[inline(always)]
     fn skip_to_end_header(&mut self) -> bool {
+        // stop after the first newline rather than at the first non-header bit,
+        // so that back-to-back header lines are not swallowed as one header
         let mask = !0 << self.pos_in_block;
-        let mut position = !self.block.header & mask;
+        let mut position = self.block.header & self.block.line_feeds & mask;
         let mut first_pos = self.pos_in_block;
         while position == 0 {
             if flag_is_set(CONFIG, COMPUTE_HEADER) && !I::RANDOM_ACCESS {
                 let header_chunk = &self.lexer.input().current_chunk()[self.pos_in_block..];
                 self.cur_header.extend_from_slice(header_chunk);
             }
-            self.block = match self.lexer.next() {
+            self.block = match self.next_block() {
                 Some(b) => b,
                 None => {
+                    // EOF in the middle of the header line: account for the
+                    // missing newline so the header range still covers the
+                    // last byte
+                    self.pos_in_block = self.lexer.input().current_chunk_len() + 1;
+                    if flag_is_set(CONFIG, COMPUTE_HEADER) && !I::RANDOM_ACCESS {
+                        self.cur_header.push(b'\n');
+                    }
                     return true;
                 }
             };
-            self.block_counter += 1;
             self.pos_in_block = 0;
             first_pos = 0;
-            position = !self.block.header;
+            position = self.block.header & self.block.line_feeds;
         }
-        self.pos_in_block = position.trailing_zeros() as usize;
+        let newline = position.trailing_zeros() as usize;
         if flag_is_set(CONFIG, COMPUTE_HEADER) && !I::RANDOM_ACCESS {
-            let header_chunk = &self.lexer.input().current_chunk()[first_pos..self.pos_in_block];
+            let header_chunk = &self.lexer.input().current_chunk()[first_pos..=newline];
             self.cur_header.extend_from_slice(header_chunk);
         }
-        false
+        if newline + 1 < 64 {
+            self.pos_in_block = newline + 1;
+            return false;
+        }
+        // the newline was the last byte of the chunk
+        match self.next_block() {
+            Some(b) => {
+                self.block = b;
+                self.pos_in_block = 0;
+                false
+            }
+            None => {
+                self.pos_in_block = self.lexer.input().current_chunk_len();
+                true
+            }
+        }
+    }
+
+    /// Validate the previous sequence line once a new one is complete,
+    /// so that the last (possibly shorter) line of a record is exempt.
+    #[inline(always)]
+    fn end_sequence_line(&mut self) {
+        let len = self.cur_line_len;
+        self.cur_line_len = 0;
+        self.seq_line_count += 1;
+        if let Some(prev) = self.prev_line_len {
+            match self.record_line_width {
+                None => self.record_line_width = Some(prev),
+                Some(width) if width != prev => self.line_uneven = true,
+                _ => {}
+            }
+        }
+        self.prev_line_len = Some(len);
     }
 
     #[inline(always)]
@@ -280,17 +629,60 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
                     128 - 2 * self.pos_in_block,
                 );
             }
+            if kmer_k(CONFIG) != 0 {
+                feed_kmers(
+                    &mut self.kmer_queue,
+                    &mut self.kmer_val,
+                    &mut self.kmer_filled,
+                    kmer_k(CONFIG),
+                    self.block.two_bits >> (2 * self.pos_in_block),
+                    self.block.len - self.pos_in_block,
+                );
+            }
             if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
-                self.dna_len += 64 - self.pos_in_block;
+                // count up to `block.len` only, like the base counts below
+                self.dna_len += self.block.len - self.pos_in_block;
+                self.actg_len += self.block.len - self.pos_in_block;
+            }
+            if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+                fold_seq_hash(
+                    &mut self.seq_hash,
+                    &self.lexer.input().current_chunk()[self.pos_in_block..],
+                );
             }
-            self.block = match self.lexer.next() {
+            if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+                // count up to `block.len` only, so that the zero padding of a
+                // final partial chunk is not mistaken for `A` bases
+                add_base_counts(
+                    &mut self.base_counts,
+                    self.block.high_bit >> self.pos_in_block,
+                    self.block.low_bit >> self.pos_in_block,
+                    self.block.len - self.pos_in_block,
+                );
+            }
+            if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+                append_bit_mask(
+                    &mut self.cur_gap_mask,
+                    &mut self.gap_mask_len,
+                    self.block.gaps >> self.pos_in_block,
+                    64 - self.pos_in_block,
+                );
+            }
+            if flag_is_set(CONFIG, COMPUTE_LINE_WIDTH) {
+                self.cur_line_len += 64 - self.pos_in_block;
+            }
+            self.block = match self.next_block() {
                 Some(b) => b,
                 None => {
                     self.pos_in_block = self.lexer.input().current_chunk_len();
+                    if flag_is_set(CONFIG, COMPUTE_LINE_WIDTH) {
+                        // the last block was only partially filled
+                        self.cur_line_len -= 64 - self.pos_in_block;
+                        self.end_sequence_line();
+                    }
                     return true;
                 }
             };
-            self.block_counter += 1;
             self.pos_in_block = 0;
             first_pos = 0;
             position = !self.block.is_dna;
@@ -313,8 +705,45 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
                 2 * (self.pos_in_block - first_pos),
             );
         }
+        if kmer_k(CONFIG) != 0 {
+            feed_kmers(
+                &mut self.kmer_queue,
+                &mut self.kmer_val,
+                &mut self.kmer_filled,
+                kmer_k(CONFIG),
+                self.block.two_bits >> (2 * first_pos),
+                self.pos_in_block - first_pos,
+            );
+        }
         if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
-            self.dna_len += self.pos_in_block;
+            self.dna_len += self.pos_in_block - first_pos;
+            self.actg_len += self.pos_in_block - first_pos;
+        }
+        if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+            fold_seq_hash(
+                &mut self.seq_hash,
+                &self.lexer.input().current_chunk()[first_pos..self.pos_in_block],
+            );
+        }
+        if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+            add_base_counts(
+                &mut self.base_counts,
+                self.block.high_bit >> first_pos,
+                self.block.low_bit >> first_pos,
+                self.pos_in_block - first_pos,
+            );
+        }
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            append_bit_mask(
+                &mut self.cur_gap_mask,
+                &mut self.gap_mask_len,
+                self.block.gaps >> first_pos,
+                self.pos_in_block - first_pos,
+            );
+        }
+        if flag_is_set(CONFIG, COMPUTE_LINE_WIDTH) {
+            self.cur_line_len += self.pos_in_block - first_pos;
+            self.end_sequence_line();
         }
         false
     }
@@ -324,14 +753,13 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastaParser<'a, CONFIG, I> {
         let mask = !0 << self.pos_in_block;
         let mut position = (self.block.is_dna | self.block.split | self.block.header) & mask;
         while position == 0 {
-            self.block = match self.lexer.next() {
+            self.block = match self.next_block() {
                 Some(b) => b,
                 None => {
                     self.pos_in_block = self.lexer.input().current_chunk_len();
                     return true;
                 }
             };
-            self.block_counter += 1;
             self.pos_in_block = 0;
             position = self.block.is_dna | self.block.split | self.block.header;
         }
@@ -346,6 +774,11 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaParser<'a, CO
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if kmer_k(CONFIG) != 0
+            && let Some(value) = self.kmer_queue.pop_front()
+        {
+            return Some(Event::Kmer(value));
+        }
         loop {
             match &self.state {
                 State::Start => {
@@ -360,15 +793,23 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaParser<'a, CO
                 State::Restart => {
                     if self.finished {
                         self.state = State::Start;
-                        if flag_is_set(CONFIG, RETURN_RECORD) {
+                        if flag_is_set(CONFIG, RETURN_RECORD)
+                            && (flag_is_not_set(CONFIG, SKIP_EMPTY_RECORDS) || self.seen_dna)
+                        {
                             return Some(Event::Record(self.global_pos()));
                         }
                         continue;
                     }
                     self.finished = self.skip_to_header_or_dna();
+                    if self.finished {
+                        // nothing but padding left, emit the final record above
+                        continue;
+                    }
                     if (1u64 << self.pos_in_block & self.block.header) != 0 {
                         self.state = State::Header;
-                        if flag_is_set(CONFIG, RETURN_RECORD) {
+                        if flag_is_set(CONFIG, RETURN_RECORD)
+                            && (flag_is_not_set(CONFIG, SKIP_EMPTY_RECORDS) || self.seen_dna)
+                        {
                             return Some(Event::Record(self.global_pos()));
                         }
                     } else if (1u64 << self.pos_in_block & self.block.is_dna) != 0 {
@@ -376,6 +817,7 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaParser<'a, CO
                     }
                 }
                 State::Header => {
+                    self.record_start = self.global_pos();
                     if flag_is_not_set(CONFIG, MERGE_RECORDS) {
                         self.clear_record();
                     }
@@ -386,11 +828,20 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaParser<'a, CO
                     if flag_is_set(CONFIG, COMPUTE_HEADER) && I::RANDOM_ACCESS {
                         self.header_range.end = self.global_pos() - 1;
                     }
-                    self.contiguous_dna = true;
+                    if flag_is_not_set(CONFIG, MERGE_RECORDS) {
+                        self.contiguous_dna = true;
+                    }
+                    self.seen_dna = false;
                     self.state = State::Restart;
                 }
                 State::StartDNA => {
                     self.state = State::InDNABlock;
+                    self.seen_dna = true;
+                    if kmer_k(CONFIG) != 0 {
+                        // a new span starts after the header or a non-ACTG
+                        // base, so the rolling window resets
+                        self.kmer_filled = 0;
+                    }
                     if flag_is_not_set(CONFIG, MERGE_DNA_CHUNKS) {
                         self.clear_chunk();
                     }
@@ -412,9 +863,13 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastaParser<'a, CO
                         continue;
                     }
                     if flag_is_set(CONFIG, COMPUTE_DNA_STRING)
+                        && flag_is_not_set(CONFIG, RAW_SEQUENCE)
                         && I::RANDOM_ACCESS
                         && self.contiguous_dna
-                        && ((1 << self.pos_in_block) & self.block.header) == 0
+                        && (((1 << self.pos_in_block) & self.block.header) == 0
+                            // flush at record boundaries too, so that merged
+                            // records keep accumulating in the buffer
+                            || flag_is_set(CONFIG, MERGE_RECORDS))
                     {
                         let dna_chunk = &self.lexer.input.data()[self.dna_range.clone()];
                         self.cur_dna_string.extend_from_slice(dna_chunk);
@@ -492,6 +947,266 @@ mod tests {
         assert_eq!(res, vec!["head", "hhh", "A B C ",]);
     }
 
+    #[test]
+    fn test_no_newline_at_all() {
+        // a file that is a single header line with no terminating newline
+        let mut f = FastaParser::<CONFIG_HEADER, _>::from_slice(b">onlyheader");
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert_eq!(f.get_header(), b"onlyheader");
+        assert!(f.next().is_none());
+
+        // the same, spanning several 64-byte chunks
+        let long = [b">".as_slice(), &[b'x'; 200]].concat();
+        let mut f = FastaParser::<CONFIG_HEADER, _>::from_slice(&long);
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert_eq!(f.get_header(), &long[1..]);
+        assert!(f.next().is_none());
+
+        // reader inputs buffer the header instead of slicing the input
+        let mut f = FastaParser::<CONFIG_HEADER, _>::from_reader(long.as_slice());
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert_eq!(f.get_header(), &long[1..]);
+        assert!(f.next().is_none());
+    }
+
+    #[test]
+    fn test_no_trailing_newline() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().config();
+        let mut f = FastaParser::<CONFIG_DEFAULT, _>::from_slice(b">h\nACGT");
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert_eq!(f.get_header(), b"h");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+        assert!(f.next().is_none());
+    }
+
+    #[test]
+    fn test_seq_hash() {
+        const CONFIG_HASH: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .compute_seq_hash()
+            .config();
+        // `a` and `b` carry the same bases with different line wrapping
+        let fasta = b">a\nACGTACGT\n>b\nACGT\nACGT\n>c\nTTTT\n";
+        let mut f = FastaParser::<CONFIG_HASH, _>::from_slice(fasta);
+        let mut hashes = Vec::new();
+        while f.next().is_some() {
+            hashes.push(f.get_seq_hash());
+        }
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+    }
+
+    #[test]
+    fn test_events_cardinality() {
+        const BASE: ParserOptions = ParserOptions::default().ignore_headers().dna_string();
+
+        const CONFIG_PER_RECORD: Config = BASE.events(Events::PerRecord).config();
+        let f = FastaParser::<CONFIG_PER_RECORD, _>::from_slice(FASTA);
+        let events: Vec<Event> = f.collect();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| matches!(e, Event::Record(_))));
+
+        // without splitting, each record's sequence is one chunk
+        const CONFIG_PER_CHUNK: Config = BASE.events(Events::PerChunk).config();
+        let f = FastaParser::<CONFIG_PER_CHUNK, _>::from_slice(FASTA);
+        let events: Vec<Event> = f.collect();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| matches!(e, Event::DnaChunk(_))));
+
+        // splitting turns every contiguous ACTG run into its own chunk
+        const CONFIG_PER_SPLIT: Config = BASE.split_non_actg().events(Events::PerChunk).config();
+        let f = FastaParser::<CONFIG_PER_SPLIT, _>::from_slice(FASTA);
+        assert_eq!(f.count(), 5);
+
+        const CONFIG_MERGED: Config = BASE.events(Events::Merged).config();
+        let mut f = FastaParser::<CONFIG_MERGED, _>::from_slice(FASTA);
+        assert!(f.next().is_none());
+        assert_eq!(
+            f.get_dna_string(),
+            b"TTTCTtaAAAAAGAAAAACAANCTCTTANNAAACAAAnAGCTTTCCAC"
+        );
+    }
+
+    #[test]
+    fn test_sequence_line_count() {
+        const CONFIG_WIDTH: Config = ParserOptions::default().compute_line_width().config();
+        let mut f = FastaParser::<CONFIG_WIDTH, _>::from_slice(FASTA);
+        assert!(f.next().is_some());
+        assert_eq!(f.sequence_line_count(), 3);
+        assert!(f.next().is_some());
+        assert_eq!(f.sequence_line_count(), 2);
+        assert!(f.next().is_some());
+        assert_eq!(f.sequence_line_count(), 1);
+    }
+
+    #[test]
+    fn test_collect_headers() {
+        let f = FastaParser::<CONFIG_HEADER, _>::from_slice(FASTA);
+        let headers = f.collect_headers();
+        assert_eq!(
+            headers,
+            [b"head".to_vec(), b"hhh".to_vec(), b"A B C ".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_custom_header_byte() {
+        const CONFIG_HASH: Config = ParserOptions::default().fasta_header_byte(b'#').config();
+        let fasta = b"#h\nACGT\nTTAA\n#i\nCCGG";
+        let mut f = FastaParser::<CONFIG_HASH, _>::from_slice(fasta);
+        let mut res = Vec::new();
+        while f.next().is_some() {
+            res.push((
+                String::from_utf8(f.get_header_owned()).unwrap(),
+                String::from_utf8(f.get_dna_string_owned()).unwrap(),
+            ));
+        }
+        assert_eq!(
+            res,
+            vec![
+                ("h".to_string(), "ACGTTTAA".to_string()),
+                ("i".to_string(), "CCGG".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clone_fork() {
+        let mut f = FastaParser::<CONFIG_STRING, _>::from_slice(FASTA);
+        f.next().unwrap();
+        let first = f.get_dna_string_owned();
+
+        // advance the clone, the original stays on the current record
+        let mut g = f.clone();
+        g.next().unwrap();
+        let second = g.get_dna_string_owned();
+        assert_ne!(first, second);
+
+        f.next().unwrap();
+        assert_eq!(f.get_dna_string_owned(), second);
+        f.next().unwrap();
+        g.next().unwrap();
+        assert_eq!(f.get_dna_string_owned(), g.get_dna_string_owned());
+        assert!(f.next().is_none());
+        assert!(g.next().is_none());
+    }
+
+    #[test]
+    fn test_stats() {
+        let f = FastaParser::<CONFIG_COLUMNAR, _>::from_slice(FASTA);
+        assert_eq!(
+            f.stats(),
+            FastxStats {
+                records: 3,
+                total_bases: 48,
+                n_bases: 4,
+                gc_bases: 11,
+                lines: 9,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut f = FastaParser::<CONFIG_HEADER, _>::from_slice(FASTA);
+        let mut res = Vec::new();
+        while let Some(_) = f.next() {
+            res.push(String::from_utf8(f.get_header_owned()).unwrap());
+        }
+        assert_eq!(res, vec!["head", "hhh", "A B C ",]);
+
+        f.reset(SliceInput::new(b">other\nACGT\n>last\nTT"));
+        let mut res = Vec::new();
+        while let Some(_) = f.next() {
+            res.push(String::from_utf8(f.get_header_owned()).unwrap());
+        }
+        assert_eq!(res, vec!["other", "last",]);
+    }
+
+    #[test]
+    fn test_gap_mask() {
+        const CONFIG_GAPS: Config = ParserOptions::default().compute_gap_mask().config();
+        let mut f = FastaParser::<CONFIG_GAPS, _>::from_slice(b">aln\nAC--GT\n");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_dna_string(), b"AC--GT");
+        assert_eq!(f.get_gap_mask(), &[0b001100]);
+    }
+
+    #[test]
+    fn test_fasta_comments() {
+        const CONFIG_COMMENTS: Config = ParserOptions::default().skip_fasta_comments().config();
+        let fasta = b">h\n;comment with junk!\nACGT\nTTAA\n;trailing comment\n>i\nCCGG\n";
+        let mut f = FastaParser::<CONFIG_COMMENTS, _>::from_slice(fasta.as_slice());
+        let mut res = Vec::new();
+        while let Some(_) = f.next() {
+            res.push((
+                String::from_utf8(f.get_header_owned()).unwrap(),
+                String::from_utf8(f.get_dna_string_owned()).unwrap(),
+            ));
+        }
+        assert_eq!(
+            res,
+            vec![
+                ("h".to_string(), "ACGTTTAA".to_string()),
+                ("i".to_string(), "CCGG".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_width() {
+        const CONFIG_WIDTH: Config = ParserOptions::default().compute_line_width().config();
+        let fasta = b">uniform\nACGTACGTACG\nACGTACGTACG\nACG\n>ragged\nACGTA\nAC\nACGTACG\n>single\nACGTAC\n";
+        let mut f = FastaParser::<CONFIG_WIDTH, _>::from_slice(fasta.as_slice());
+        assert!(f.next().is_some());
+        // a shorter final line does not make the record uneven
+        assert_eq!(f.line_width(), Some(11));
+        assert!(f.next().is_some());
+        assert_eq!(f.line_width(), None);
+        assert!(f.next().is_some());
+        assert_eq!(f.line_width(), Some(6));
+    }
+
+    #[test]
+    fn test_raw_sequence() {
+        const CONFIG_RAW: Config = ParserOptions::default()
+            .ignore_headers()
+            .dna_string()
+            .raw_sequence()
+            .config();
+        let mut f = FastaParser::<CONFIG_RAW, _>::from_slice(FASTA);
+        let mut res = Vec::new();
+        while let Some(_) = f.next() {
+            res.push(String::from_utf8(f.get_dna_string().to_vec()).unwrap());
+        }
+        // multi-line sequences keep their line feeds, single-line ones are unchanged
+        assert_eq!(
+            res,
+            vec![
+                "TTTCTtaAAAA\nAGAAAA\nACAAN",
+                "CTCTTANNAAA\nCAAAnAGCTTT",
+                "CCAC",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dna_string_into() {
+        let mut f = FastaParser::<CONFIG_STRING, _>::from_slice(FASTA);
+        let mut buf = Vec::new();
+        let mut res = Vec::new();
+        while let Some(_) = f.next() {
+            f.get_dna_string_into(&mut buf);
+            res.push(String::from_utf8(buf.clone()).unwrap());
+        }
+        assert_eq!(
+            res,
+            vec!["TTTCTtaAAAAAGAAAAACAAN", "CTCTTANNAAACAAAnAGCTTT", "CCAC",]
+        );
+    }
+
     #[test]
     fn test_dna_string() {
         let mut f = FastaParser::<CONFIG_STRING, _>::from_slice(FASTA);
@@ -597,4 +1312,341 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_for_each_record_fields() {
+        let mut f = FastaParser::<DEFAULT_CONFIG, _>::from_slice(FASTA);
+        let mut res = Vec::new();
+        f.for_each_record_fields(|header, seq, qual| {
+            assert!(qual.is_none());
+            res.push((header.to_vec(), seq.to_vec()));
+        });
+        assert_eq!(
+            res,
+            vec![
+                (b"head".to_vec(), b"TTTCTtaAAAAAGAAAAACAAN".to_vec()),
+                (b"hhh".to_vec(), b"CTCTTANNAAACAAAnAGCTTT".to_vec()),
+                (b"A B C ".to_vec(), b"CCAC".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_bytes() {
+        // concatenating the raw record bytes reconstructs the input verbatim
+        let mut f = FastaParser::<DEFAULT_CONFIG, _>::from_slice(FASTA);
+        let mut raw = Vec::new();
+        while f.next().is_some() {
+            raw.extend_from_slice(f.record_bytes().unwrap());
+        }
+        assert_eq!(raw, FASTA);
+
+        // reader inputs do not keep the input around
+        let mut f = FastaParser::<DEFAULT_CONFIG, _>::from_reader(FASTA);
+        assert!(f.next().is_some());
+        assert!(f.record_bytes().is_none());
+    }
+
+    #[test]
+    fn test_merge_records() {
+        // all sequences accumulate into one, headers are not merged
+        const CONFIG_MERGE: Config = ParserOptions::default().merge_records().config();
+        let mut f = FastaParser::<CONFIG_MERGE, _>::from_slice(FASTA);
+        while f.next().is_some() {}
+        assert_eq!(
+            f.get_dna_string(),
+            b"TTTCTtaAAAAAGAAAAACAANCTCTTANNAAACAAAnAGCTTTCCAC"
+        );
+        assert_eq!(f.get_header(), b"A B C ");
+
+        const CONFIG_MERGE_PACKED: Config = ParserOptions::default()
+            .ignore_headers()
+            .dna_packed()
+            .skip_non_actg()
+            .merge_records()
+            .config();
+        let mut f = FastaParser::<CONFIG_MERGE_PACKED, _>::from_slice(FASTA);
+        while f.next().is_some() {}
+        assert_eq!(
+            format!("{}", f.get_dna_packed()),
+            "TTTCTTAAAAAAGAAAAACAACTCTTAAAACAAAAGCTTTCCAC"
+        );
+    }
+
+    #[test]
+    fn test_empty_records() {
+        let fasta = b">empty\n>a\nACGT\n>empty2\n";
+
+        // empty records still emit, with an empty sequence and a zero length
+        const CONFIG_LEN: Config = DEFAULT_CONFIG | COMPUTE_DNA_LEN;
+        let mut f = FastaParser::<CONFIG_LEN, _>::from_slice(fasta);
+        let mut res = Vec::new();
+        f.for_each_record_fields(|header, seq, _| {
+            res.push((header.to_vec(), seq.to_vec()));
+        });
+        assert_eq!(
+            res,
+            vec![
+                (b"empty".to_vec(), Vec::new()),
+                (b"a".to_vec(), b"ACGT".to_vec()),
+                (b"empty2".to_vec(), Vec::new()),
+            ]
+        );
+        let mut f = FastaParser::<CONFIG_LEN, _>::from_slice(b">empty\n>a\nACGT");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_dna_len(), 0);
+
+        // with SKIP_EMPTY_RECORDS, only records with sequence emit
+        const CONFIG_SKIP: Config = ParserOptions::default().skip_empty_records().config();
+        let mut f = FastaParser::<CONFIG_SKIP, _>::from_slice(fasta);
+        let mut res = Vec::new();
+        f.for_each_record_fields(|header, seq, _| {
+            res.push((header.to_vec(), seq.to_vec()));
+        });
+        assert_eq!(res, vec![(b"a".to_vec(), b"ACGT".to_vec())]);
+    }
+
+    #[test]
+    fn test_emit_kmers() {
+        const K: usize = 5;
+        const CONFIG_KMER: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .emit_kmers(K as u8)
+            .config();
+        let fasta = b">r\nACGTACGTTGCANGGATCCA\nTTGA\n";
+        let mut kmers = Vec::new();
+        let f = FastaParser::<CONFIG_KMER, _>::from_slice(fasta.as_slice());
+        for event in f {
+            if let Event::Kmer(value) = event {
+                kmers.push(value);
+            }
+        }
+        // the window resets at the `N` but not at the line break
+        let mut expected = Vec::new();
+        for span in ["ACGTACGTTGCA", "GGATCCATTGA"] {
+            let mut packed = PackedDNA::new();
+            packed.push_str(span);
+            expected.extend(packed.kmers(K));
+        }
+        assert_eq!(kmers, expected);
+    }
+
+    #[test]
+    fn test_dna_len_only() {
+        const CONFIG_LEN: Config = ParserOptions::default().dna_len_only().config();
+        const CONFIG_LEN_ACTG: Config = ParserOptions::default()
+            .dna_len_only()
+            .skip_non_actg()
+            .config();
+
+        // without SPLIT_NON_ACTG, the Ns count toward the length
+        let mut f = FastaParser::<CONFIG_LEN, _>::from_slice(FASTA);
+        let mut s = FastaParser::<CONFIG_STRING, _>::from_slice(FASTA);
+        while f.next().is_some() {
+            assert!(s.next().is_some());
+            assert_eq!(f.get_dna_len(), s.get_dna_string().len());
+        }
+
+        // with skip_non_actg, the raw length still counts the skipped bases,
+        // the ACTG-only count is a separate accumulator
+        let mut f = FastaParser::<CONFIG_LEN_ACTG, _>::from_slice(FASTA);
+        let mut s = FastaParser::<CONFIG_STRING, _>::from_slice(FASTA);
+        let mut a = FastaParser::<CONFIG_STRING_ACTG_MERGE, _>::from_slice(FASTA);
+        while f.next().is_some() {
+            assert!(s.next().is_some());
+            assert!(a.next().is_some());
+            assert_eq!(f.get_dna_len(), s.get_dna_string().len());
+            assert_eq!(f.get_actg_len(), a.get_dna_string().len());
+        }
+    }
+
+    #[test]
+    fn test_actg_len() {
+        const CONFIG_LEN_ACTG: Config = ParserOptions::default()
+            .dna_len_only()
+            .skip_non_actg()
+            .config();
+        let mut f = FastaParser::<CONFIG_LEN_ACTG, _>::from_slice(b">r\nACNNGT\n");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_dna_len(), 6);
+        assert_eq!(f.get_actg_len(), 4);
+    }
+
+    #[test]
+    fn test_protein_alphabet() {
+        const CONFIG_PROTEIN: Config = ParserOptions::default()
+            .skip_non_actg()
+            .alphabet_protein()
+            .config();
+        let fasta = b">prot\nMKVL*TP\nEFX-GH*\n";
+        let mut f = FastaParser::<CONFIG_PROTEIN, _>::from_slice(fasta.as_slice());
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"prot");
+        // the stop codons and the `X` are kept, the alignment gap still splits
+        assert_eq!(f.get_dna_string(), b"MKVL*TPEFXGH*");
+    }
+
+    #[test]
+    fn test_rna_alphabet() {
+        const CONFIG_RNA: Config = ParserOptions::default()
+            .skip_non_actg()
+            .alphabet_rna()
+            .config();
+        let fasta = b">rna\nACGUacgu\n";
+        let mut f = FastaParser::<CONFIG_RNA, _>::from_slice(fasta.as_slice());
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"rna");
+        // `U`/`u` are members of the alphabet, so the sequence is not split
+        assert_eq!(f.get_dna_string(), b"ACGUacgu");
+    }
+
+    #[test]
+    fn test_header_stripped() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().config();
+        let mut f = FastaParser::<CONFIG_DEFAULT, _>::from_slice(b">lcl|chr1\nACGT\n>chr2\nTTAA\n");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header_stripped(b"lcl|"), b"chr1");
+        assert!(f.next().is_some());
+        // a header without the prefix is returned unchanged
+        assert_eq!(f.get_header_stripped(b"lcl|"), b"chr2");
+    }
+
+    #[test]
+    fn test_into_input() {
+        let mut f = FastaParser::<CONFIG_HEADER, _>::from_slice(FASTA);
+        while f.next().is_some() {}
+        assert!(f.input().is_end_of_buffer());
+        let input = f.into_input();
+        // the reclaimed input still exposes the parsed data
+        assert_eq!(input.data(), FASTA);
+    }
+
+    #[test]
+    fn test_take_records() {
+        const CONFIG_PACKED_RECORDS: Config = ParserOptions::default()
+            .ignore_headers()
+            .dna_packed()
+            .config();
+        // each record splits into several chunks at the `N`s
+        let fasta = b">a\nACGTNNAC\n>b\nTTNAA\n>c\nGGGG\n";
+        let f = FastaParser::<CONFIG_PACKED_RECORDS, _>::from_slice(fasta.as_slice());
+        let events: Vec<Event> = f.take_records(2).collect();
+        let records = events
+            .iter()
+            .filter(|event| matches!(event, Event::Record(_)))
+            .count();
+        let chunks = events
+            .iter()
+            .filter(|event| matches!(event, Event::DnaChunk(_)))
+            .count();
+        // exactly 2 records, with all 4 of their chunks interleaved
+        assert_eq!(records, 2);
+        assert_eq!(chunks, 4);
+    }
+
+    #[test]
+    fn test_merge_flush_at_eof() {
+        const CONFIG_MERGE_NO_RECORD: Config = (ParserOptions::default()
+            .ignore_headers()
+            .dna_packed()
+            .skip_non_actg()
+            .config()
+            | COMPUTE_DNA_STRING)
+            & !RETURN_RECORD;
+        // the input ends in the middle of a merged sequence, with no newline
+        let fasta = b">h\nACGTNNAC\nGTAC";
+        let mut f = FastaParser::<CONFIG_MERGE_NO_RECORD, _>::from_slice(fasta.as_slice());
+        while f.next().is_some() {}
+        // the trailing accumulation is still accessible after the iterator
+        assert_eq!(f.get_dna_string(), b"ACGTACGTAC");
+        assert!(*f.get_dna_packed() == "ACGTACGTAC");
+    }
+
+    #[test]
+    fn test_current_line() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().config();
+        let mut f = FastaParser::<CONFIG_DEFAULT, _>::from_slice(FASTA);
+        assert!(f.next().is_some());
+        // the first record's event fires on the `>hhh` header, line 6
+        assert_eq!(f.current_line(), 6);
+    }
+
+    #[test]
+    fn test_try_accessors() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().config();
+        let mut f = FastaParser::<CONFIG_DEFAULT, _>::from_slice(FASTA);
+        assert!(f.next().is_some());
+        assert_eq!(f.try_get_header(), Some(b"head".as_slice()));
+        assert_eq!(
+            f.try_get_dna_string(),
+            Some(b"TTTCTtaAAAAAGAAAAACAAN".as_slice())
+        );
+        // quality is never computed for FASTA, with no panic
+        assert_eq!(f.try_get_quality(), None);
+
+        const CONFIG_NO_DNA: Config = ParserOptions::default().ignore_dna().config();
+        let mut f = FastaParser::<CONFIG_NO_DNA, _>::from_slice(FASTA);
+        assert!(f.next().is_some());
+        assert_eq!(f.try_get_dna_string(), None);
+    }
+
+    #[test]
+    fn test_sequence_entropy() {
+        const CONFIG_COUNTS: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .compute_base_counts()
+            .config();
+        let fasta = b">a\nAAAAAAAA\n>b\nACGTACGT\n>c\nAAAAAACC\n";
+        let mut f = FastaParser::<CONFIG_COUNTS, _>::from_slice(fasta.as_slice());
+        assert!(f.next().is_some());
+        // a homopolymer has zero entropy
+        assert_eq!(f.sequence_entropy(), 0.0);
+        assert!(f.is_low_complexity(1.0));
+        assert!(f.next().is_some());
+        // a uniform base mix has the full two bits
+        assert!((f.sequence_entropy() - 2.0).abs() < 1e-12);
+        assert!(!f.is_low_complexity(1.0));
+        assert!(f.next().is_some());
+        // 3/4 A and 1/4 C
+        assert!((f.sequence_entropy() - 0.8112781244591328).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_base_counts() {
+        const CONFIG_COUNTS: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .compute_base_counts()
+            .config();
+        const CONFIG_COUNTS_ACTG: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .compute_base_counts()
+            .skip_non_actg()
+            .config();
+
+        // a record whose sequence fills exactly one 64-byte chunk
+        let mut fasta = b">r\n".to_vec();
+        fasta.extend_from_slice(&[b'A'; 10]);
+        fasta.extend_from_slice(&[b'C'; 20]);
+        fasta.extend_from_slice(&[b'T'; 30]);
+        fasta.extend_from_slice(&[b'G'; 4]);
+        fasta.push(b'\n');
+        let mut f = FastaParser::<CONFIG_COUNTS, _>::from_slice(&fasta);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_base_counts(), [10, 20, 30, 4]);
+        assert!(f.next().is_none());
+
+        // without SPLIT_NON_ACTG, N aliases to G through its 2-bit code
+        let mut f = FastaParser::<CONFIG_COUNTS, _>::from_slice(b">r\nACGTN\n");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_base_counts(), [1, 1, 1, 2]);
+
+        // with SPLIT_NON_ACTG, only the ACTG positions are counted
+        let mut f = FastaParser::<CONFIG_COUNTS_ACTG, _>::from_slice(b">r\nACGTN\n");
+        assert!(f.next().is_some());
+        assert_eq!(f.get_base_counts(), [1, 1, 1, 1]);
+    }
 }
@@ -8,12 +8,16 @@ use core::mem::swap;
 use core::ops::Range;
 
 /// A parser for the [FASTQ format](https://en.wikipedia.org/wiki/FASTQ_format).
+///
+/// With a cloneable input (e.g. [`SliceInput`]), the parser itself is `Clone`:
+/// a clone can be advanced for speculative lookahead while the original stays
+/// on the current record.
+#[derive(Clone)]
 pub struct FastqParser<'a, const CONFIG: Config, I: InputData<'a>> {
     lexer: FastqLexer<'a, CONFIG, I>,
     finished: bool,
     line_count: usize,
     block: FastqChunk,
-    block_counter: usize,
     pos_in_block: usize,
     header_range: Range<usize>,
     quality_range: Range<usize>,
@@ -24,6 +28,20 @@ pub struct FastqParser<'a, const CONFIG: Config, I: InputData<'a>> {
     cur_dna_columnar: ColumnarDNA,
     cur_dna_packed: PackedDNA,
     dna_len: usize,
+    actg_len: usize,
+    seq_hash: u64,
+    base_counts: [usize; 4],
+    kmer_val: u64,
+    kmer_filled: usize,
+    kmer_queue: std::collections::VecDeque<u64>,
+    peeked: Option<Event>,
+    truncated: bool,
+    cur_gap_mask: Vec<u64>,
+    gap_mask_len: usize,
+    record_start: usize,
+    val_line_start: usize,
+    val_seq_len: usize,
+    validation_error: Option<ParseError>,
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FastqParser<'a, CONFIG, I> {
@@ -41,7 +59,6 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastqParser<'a, CONFIG, I> {
             finished,
             line_count: 0,
             block,
-            block_counter: 0,
             pos_in_block: 0,
             header_range: 0..0,
             quality_range: 0..0,
@@ -52,8 +69,292 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastqParser<'a, CONFIG, I> {
             cur_dna_columnar: ColumnarDNA::new(),
             cur_dna_packed: PackedDNA::new(),
             dna_len: 0,
+            actg_len: 0,
+            seq_hash: 0,
+            base_counts: [0; 4],
+            kmer_val: 0,
+            kmer_filled: 0,
+            kmer_queue: std::collections::VecDeque::new(),
+            peeked: None,
+            truncated: false,
+            cur_gap_mask: Vec::new(),
+            gap_mask_len: 0,
+            record_start: 0,
+            val_line_start: 0,
+            val_seq_len: 0,
+            validation_error: None,
         }
     }
+
+    /// Reset the parser on a new input, reusing the existing allocations.
+    pub fn reset(&mut self, input: I) {
+        self.lexer.reset(input);
+        self.finished = false;
+        self.block = match self.lexer.next() {
+            Some(c) => c,
+            None => {
+                self.finished = true;
+                FastqChunk::default()
+            }
+        };
+        self.line_count = 0;
+        self.pos_in_block = 0;
+        self.header_range = 0..0;
+        self.quality_range = 0..0;
+        self.dna_range = 0..0;
+        self.cur_header.clear();
+        self.cur_quality.clear();
+        self.cur_dna_string.clear();
+        self.cur_dna_columnar.clear();
+        self.cur_dna_packed.clear();
+        self.dna_len = 0;
+        self.actg_len = 0;
+        self.seq_hash = 0;
+        self.base_counts = [0; 4];
+        self.kmer_val = 0;
+        self.kmer_filled = 0;
+        self.kmer_queue.clear();
+        self.peeked = None;
+        self.truncated = false;
+        self.cur_gap_mask.clear();
+        self.gap_mask_len = 0;
+        self.record_start = 0;
+        self.val_line_start = 0;
+        self.val_seq_len = 0;
+        self.validation_error = None;
+    }
+
+    /// Build a parser after checking that the input starts at a record
+    /// boundary: an `@` header line followed by a sequence line and a `+`
+    /// separator line.
+    ///
+    /// The line-count state machine assumes the input starts at a record
+    /// boundary; a fragment starting mid-record (e.g. inside a quality line
+    /// that happens to begin with `@`) would silently misparse, so mid-stream
+    /// starts are unsupported.
+    /// The check only sees the initially buffered bytes of reader inputs, so
+    /// a `+` separator beyond them is not verified.
+    pub fn from_input_at_boundary(input: I) -> Result<Self, ParseError> {
+        if input.first_byte() != b'@' {
+            return Err(ParseError::MissingAt { line: 1 });
+        }
+        let buffer = input.buffer();
+        // reader buffers are zero-padded past the bytes read so far
+        let available = buffer
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(buffer.len());
+        let mut lines = buffer[..available].split(|&byte| byte == b'\n');
+        let _header = lines.next();
+        let _seq = lines.next();
+        match lines.next() {
+            // an empty third fragment means the separator line is not
+            // buffered yet, which cannot be told apart from truncation
+            Some([b'+', ..]) | Some([]) | None => Ok(Self::from_input(input)),
+            Some(_) => Err(ParseError::MissingPlus { line: 3 }),
+        }
+    }
+
+    /// Iterate `(base, Phred score)` pairs of the current record, decoding
+    /// the quality bytes with the given `offset` (usually 33).
+    /// The pairing is positional, so it is only valid when the sequence bytes
+    /// are kept verbatim, i.e. [`dna_string`](crate::ParserOptions::dna_string)
+    /// with [`keep_non_actg`](crate::ParserOptions::keep_non_actg); this is
+    /// asserted, as the packed and split modes may drop bases.
+    /// This returns `None` when no quality line is available.
+    pub fn base_qual_pairs(&self, offset: u8) -> Option<impl Iterator<Item = (u8, u8)> + '_> {
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_STRING));
+        assert!(flag_is_not_set(CONFIG, SPLIT_NON_ACTG));
+        let quality = self.get_quality()?;
+        Some(
+            self.get_dna_string()
+                .iter()
+                .zip(quality)
+                .map(move |(&base, &q)| (base, q.saturating_sub(offset))),
+        )
+    }
+
+    /// Sum the Phred error probabilities `10^(-Q/10)` over the current
+    /// record's quality, decoded with the given `offset` (usually 33) and
+    /// clamped to the 64-entry probability table.
+    /// This is the expected number of errors in the read, a common filtering
+    /// statistic.
+    /// This returns `None` when no quality line is available.
+    pub fn expected_errors(&self, offset: u8) -> Option<f64> {
+        let quality = self.get_quality()?;
+        let table = phred_error_table();
+        Some(
+            quality
+                .iter()
+                .map(|&q| table[(q.saturating_sub(offset) as usize).min(table.len() - 1)])
+                .sum(),
+        )
+    }
+
+    /// Decode the current quality line into Phred scores, using the offset
+    /// from [`phred_offset`](crate::ParserOptions::phred_offset) (default 33)
+    /// so that callers do not re-specify the encoding per call.
+    /// This returns `None` when no quality line is available.
+    pub fn get_quality_scores(&self) -> Option<Vec<u8>> {
+        let offset = phred_offset(CONFIG);
+        Some(
+            self.get_quality()?
+                .iter()
+                .map(|&q| q.saturating_sub(offset))
+                .collect(),
+        )
+    }
+
+    /// Detect the Phred quality offset by sampling the quality bytes of up to
+    /// the next 100 records: a byte below `;` implies phred33 (`Some(33)`), a
+    /// byte above `J` implies phred64 (`Some(64)`), and `None` is returned
+    /// when the observed range fits both encodings.
+    /// This consumes the sampled records, so it is best run on a clone of the
+    /// parser over random-access input, keeping the original at the start.
+    /// This requires the [`COMPUTE_QUALITY`] and [`RETURN_RECORD`] flags.
+    pub fn detect_quality_offset(&mut self) -> Option<u8> {
+        assert!(flag_is_set(CONFIG, COMPUTE_QUALITY));
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        let mut sampled = 0;
+        while sampled < 100 {
+            match self.next() {
+                Some(Event::Record(_)) => {
+                    let quality = self.get_quality()?;
+                    if quality.iter().any(|&q| q < 33 + 26) {
+                        return Some(33);
+                    }
+                    if quality.iter().any(|&q| q > 33 + 41) {
+                        return Some(64);
+                    }
+                    sampled += 1;
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+        None
+    }
+
+    /// Peek at the next record's header without consuming its
+    /// [`Record`](Event::Record) event: the record is parsed and buffered, and
+    /// the following call to `next` still surfaces it.
+    /// The accessors already reflect the peeked record, so this can drive
+    /// lookahead-based logic such as batch boundaries.
+    /// This requires the [`COMPUTE_HEADER`] and [`RETURN_RECORD`] flags, and is
+    /// not supported together with per-chunk or k-mer events, which would be
+    /// skipped while looking for the record.
+    pub fn peek_header(&mut self) -> Option<&[u8]> {
+        assert!(flag_is_set(CONFIG, COMPUTE_HEADER));
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        assert!(flag_is_not_set(CONFIG, RETURN_DNA_CHUNK));
+        assert!(kmer_k(CONFIG) == 0);
+        if self.peeked.is_none() {
+            let event = self.next()?;
+            self.peeked = Some(event);
+        }
+        Some(self.get_header())
+    }
+
+    /// Consume the parser, yielding records grouped into owned batches of
+    /// `batch_size` (the final batch may be shorter).
+    /// The reading stays single-threaded, but the owned batches can be handed
+    /// to a thread pool (e.g. `rayon`) for per-record work, and batching
+    /// amortizes the handoff.
+    /// Only the fields computed by the configuration are populated.
+    /// This requires the [`RETURN_RECORD`] flag.
+    pub fn batched_owned(mut self, batch_size: usize) -> impl Iterator<Item = Vec<OwnedRecord>> + 'a
+    where
+        I: 'a,
+    {
+        assert!(batch_size >= 1);
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        std::iter::from_fn(move || {
+            let mut batch = Vec::with_capacity(batch_size);
+            while batch.len() < batch_size {
+                match self.next() {
+                    Some(Event::Record(_)) => batch.push(OwnedRecord {
+                        header: if flag_is_set(CONFIG, COMPUTE_HEADER) {
+                            self.get_header_owned()
+                        } else {
+                            Vec::new()
+                        },
+                        seq: if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
+                            self.get_dna_string_owned()
+                        } else {
+                            Vec::new()
+                        },
+                        qual: if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+                            self.get_quality_owned()
+                        } else {
+                            None
+                        },
+                    }),
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+            if batch.is_empty() { None } else { Some(batch) }
+        })
+    }
+
+    /// Get a reference to the underlying input, e.g. to check
+    /// [`buffer_offset`](InputData::buffer_offset) or
+    /// [`is_end_of_buffer`](InputData::is_end_of_buffer) mid-iteration.
+    #[inline(always)]
+    pub fn input(&self) -> &I {
+        &self.lexer.input
+    }
+
+    /// Consume the parser and reclaim the underlying input, e.g. to read
+    /// trailing metadata after the iterator finishes.
+    #[inline(always)]
+    pub fn into_input(self) -> I {
+        self.lexer.input
+    }
+
+    /// Whether the input ended mid-record: inside a header, sequence, plus or
+    /// quality line with no terminating newline, e.g. a truncated download or
+    /// an early end of a corrupt gzip stream.
+    /// A final quality line ending exactly at the end of the input without a
+    /// trailing newline also counts, since it cannot be told apart from a
+    /// cut-off quality line (enable [`validate`](crate::ParserOptions::validate)
+    /// to check its length instead).
+    #[inline(always)]
+    pub fn was_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Consume the parser and compute [`FastxStats`] in a single pass over the records.
+    /// This requires the [`COMPUTE_DNA_STRING`] and [`RETURN_RECORD`] flags.
+    pub fn stats(mut self) -> FastxStats {
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_STRING));
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        let mut stats = FastxStats::default();
+        while let Some(event) = self.next() {
+            if let Event::Record(_) = event {
+                stats.records += 1;
+                for &x in self.get_dna_string() {
+                    stats.total_bases += 1;
+                    match x & UPPERCASE_MASK {
+                        b'C' | b'G' => stats.gc_bases += 1,
+                        b'A' | b'T' => {}
+                        _ => stats.n_bases += 1,
+                    }
+                }
+            }
+        }
+        stats.lines = self.line_count;
+        stats
+    }
+}
+
+const UPPERCASE_MASK: u8 = 0b11011111;
+
+/// The error probabilities for Phred scores 0..64, computed once to avoid a
+/// `powf` per base.
+fn phred_error_table() -> &'static [f64; 64] {
+    static TABLE: std::sync::OnceLock<[f64; 64]> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| core::array::from_fn(|q| 10f64.powf(-(q as f64) / 10.0)))
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FromInputData<'a, I>
@@ -70,6 +371,12 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastqParser<'a, CONF
         Format::Fastq
     }
 
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn lexer_stats(&self) -> crate::lexer::LexerStats {
+        self.lexer.stats()
+    }
+
     #[inline(always)]
     fn clear_record(&mut self) {
         if flag_is_set(CONFIG, COMPUTE_HEADER) {
@@ -94,6 +401,17 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastqParser<'a, CONF
         }
         if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
             self.dna_len = 0;
+            self.actg_len = 0;
+        }
+        if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+            self.seq_hash = 0;
+        }
+        if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+            self.base_counts = [0; 4];
+        }
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            self.cur_gap_mask.clear();
+            self.gap_mask_len = 0;
         }
     }
 
@@ -196,12 +514,84 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Parser for FastqParser<'a, CONF
         assert!(flag_is_set(CONFIG, COMPUTE_DNA_LEN));
         self.dna_len
     }
+
+    #[inline(always)]
+    fn get_actg_len(&self) -> usize {
+        assert!(flag_is_set(CONFIG, COMPUTE_DNA_LEN));
+        assert!(flag_is_set(CONFIG, SPLIT_NON_ACTG));
+        self.actg_len
+    }
+
+    #[inline(always)]
+    fn get_seq_hash(&self) -> u64 {
+        assert!(flag_is_set(CONFIG, COMPUTE_SEQ_HASH));
+        self.seq_hash
+    }
+
+    #[inline(always)]
+    fn compression(&mut self) -> std::io::Result<Option<deko::Format>> {
+        self.lexer.input.compression_format()
+    }
+
+    #[inline(always)]
+    fn get_gap_mask(&self) -> &[u64] {
+        assert!(flag_is_set(CONFIG, COMPUTE_GAP_MASK));
+        &self.cur_gap_mask
+    }
+
+    #[inline(always)]
+    fn try_get_header(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_HEADER) {
+            Some(self.get_header())
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_dna_string(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
+            Some(self.get_dna_string())
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_quality(&self) -> Option<&[u8]> {
+        if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+            self.get_quality()
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn current_line(&self) -> usize {
+        self.line_count + 1
+    }
+
+    #[inline(always)]
+    fn get_base_counts(&self) -> [usize; 4] {
+        assert!(flag_is_set(CONFIG, COMPUTE_BASE_COUNTS));
+        self.base_counts
+    }
+
+    #[inline(always)]
+    fn record_bytes(&self) -> Option<&[u8]> {
+        if I::RANDOM_ACCESS {
+            Some(&self.lexer.input.data()[self.record_start..self.global_pos()])
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a, const CONFIG: Config, I: InputData<'a>> FastqParser<'a, CONFIG, I> {
+    /// Offset of the current byte from the start of the (decompressed) input.
     #[inline(always)]
-    const fn global_pos(&self) -> usize {
-        64 * self.block_counter + self.pos_in_block
+    fn global_pos(&self) -> usize {
+        self.lexer.input.chunk_offset() + self.pos_in_block
     }
 
     #[inline(always)]
@@ -212,7 +602,6 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> FastqParser<'a, CONFIG, I> {
             match self.lexer.next() {
                 Some(b) => {
                     self.block = b;
-                    self.block_counter += 1;
                     self.pos_in_block = 0;
                 }
                 None => self.finished = true,
@@ -233,10 +622,42 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.peeked.take() {
+            return Some(event);
+        }
+        if flag_is_set(CONFIG, VALIDATE) && self.validation_error.is_some() {
+            return None;
+        }
+        if kmer_k(CONFIG) != 0
+            && let Some(value) = self.kmer_queue.pop_front()
+        {
+            return Some(Event::Kmer(value));
+        }
         loop {
             match self.line_count % 4 {
                 0 => {
                     // HEADER
+                    if flag_is_set(CONFIG, TOLERATE_BLANK_LINES) {
+                        // skip stray blank lines between records without
+                        // counting them toward the 4-line cycle
+                        while !self.finished
+                            && ((1u64 << self.pos_in_block) & self.block.newline) != 0
+                        {
+                            self.block.newline &= self.block.newline.wrapping_sub(1);
+                            self.increment_pos();
+                        }
+                    }
+                    self.record_start = self.global_pos();
+                    if flag_is_set(CONFIG, VALIDATE)
+                        && !self.finished
+                        && self.lexer.input.current_chunk()[self.pos_in_block] != b'@'
+                    {
+                        self.validation_error = Some(ParseError::MissingAt {
+                            line: self.line_count + 1,
+                        });
+                        self.finished = true;
+                        return None;
+                    }
                     self.increment_pos();
                     if self.finished {
                         return None;
@@ -257,10 +678,10 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                             Some(b) => b,
                             None => {
                                 self.finished = true;
+                                self.truncated = true;
                                 return None;
                             }
                         };
-                        self.block_counter += 1;
                         self.pos_in_block = 0;
                         first_pos = 0;
                     }
@@ -274,27 +695,47 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                         self.cur_header.extend_from_slice(header_chunk);
                     }
                     self.consume_newline();
+                    if flag_is_set(CONFIG, VALIDATE) {
+                        self.val_line_start = self.global_pos();
+                    }
                 }
                 1 => {
                     // SEQUENCE
+                    if kmer_k(CONFIG) != 0 {
+                        // a new span starts after the header or a non-ACTG
+                        // base, so the rolling window resets
+                        self.kmer_filled = 0;
+                    }
                     if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
                         // skip to dna or newline
                         let mask = !0 << self.pos_in_block;
                         let mut position = (self.block.is_dna | self.block.newline) & mask;
+                        let mut first_pos = self.pos_in_block;
                         while position == 0 {
+                            if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
+                                // skipped non-ACTG bases still count toward the raw length
+                                self.dna_len += self.block.len.saturating_sub(first_pos);
+                            }
                             self.block = match self.lexer.next() {
                                 Some(b) => b,
                                 None => {
                                     self.finished = true;
+                                    self.truncated = true;
                                     return None;
                                 }
                             };
-                            self.block_counter += 1;
                             self.pos_in_block = 0;
+                            first_pos = 0;
                             position = self.block.is_dna | self.block.newline;
                         }
                         self.pos_in_block = position.trailing_zeros() as usize;
+                        if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
+                            self.dna_len += self.pos_in_block - first_pos;
+                        }
                         if ((1 << self.pos_in_block) & self.block.newline) != 0 {
+                            if flag_is_set(CONFIG, VALIDATE) {
+                                self.val_seq_len = self.global_pos() - self.val_line_start;
+                            }
                             self.consume_newline();
                             continue;
                         }
@@ -333,17 +774,53 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                                 128 - 2 * self.pos_in_block,
                             );
                         }
+                        if kmer_k(CONFIG) != 0 {
+                            feed_kmers(
+                                &mut self.kmer_queue,
+                                &mut self.kmer_val,
+                                &mut self.kmer_filled,
+                                kmer_k(CONFIG),
+                                self.block.two_bits >> (2 * self.pos_in_block),
+                                self.block.len - self.pos_in_block,
+                            );
+                        }
                         if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
-                            self.dna_len += 64 - self.pos_in_block;
+                            // count up to `block.len` only, like the base counts below
+                            self.dna_len += self.block.len - self.pos_in_block;
+                            self.actg_len += self.block.len - self.pos_in_block;
+                        }
+                        if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+                            fold_seq_hash(
+                                &mut self.seq_hash,
+                                &self.lexer.input.current_chunk()[self.pos_in_block..],
+                            );
+                        }
+                        if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+                            // count up to `block.len` only, so that the zero padding of a
+                            // final partial chunk is not mistaken for `A` bases
+                            add_base_counts(
+                                &mut self.base_counts,
+                                self.block.high_bit >> self.pos_in_block,
+                                self.block.low_bit >> self.pos_in_block,
+                                self.block.len - self.pos_in_block,
+                            );
+                        }
+                        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+                            append_bit_mask(
+                                &mut self.cur_gap_mask,
+                                &mut self.gap_mask_len,
+                                self.block.gaps >> self.pos_in_block,
+                                64 - self.pos_in_block,
+                            );
                         }
                         self.block = match self.lexer.next() {
                             Some(b) => b,
                             None => {
                                 self.finished = true;
+                                self.truncated = true;
                                 return None;
                             }
                         };
-                        self.block_counter += 1;
                         self.pos_in_block = 0;
                         first_pos = 0;
                         position = !self.block.is_dna;
@@ -369,8 +846,41 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                             2 * (self.pos_in_block - first_pos),
                         );
                     }
+                    if kmer_k(CONFIG) != 0 {
+                        feed_kmers(
+                            &mut self.kmer_queue,
+                            &mut self.kmer_val,
+                            &mut self.kmer_filled,
+                            kmer_k(CONFIG),
+                            self.block.two_bits >> (2 * first_pos),
+                            self.pos_in_block - first_pos,
+                        );
+                    }
                     if flag_is_set(CONFIG, COMPUTE_DNA_LEN) {
-                        self.dna_len += self.pos_in_block;
+                        self.dna_len += self.pos_in_block - first_pos;
+                        self.actg_len += self.pos_in_block - first_pos;
+                    }
+                    if flag_is_set(CONFIG, COMPUTE_SEQ_HASH) {
+                        fold_seq_hash(
+                            &mut self.seq_hash,
+                            &self.lexer.input.current_chunk()[first_pos..self.pos_in_block],
+                        );
+                    }
+                    if flag_is_set(CONFIG, COMPUTE_BASE_COUNTS) {
+                        add_base_counts(
+                            &mut self.base_counts,
+                            self.block.high_bit >> first_pos,
+                            self.block.low_bit >> first_pos,
+                            self.pos_in_block - first_pos,
+                        );
+                    }
+                    if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+                        append_bit_mask(
+                            &mut self.cur_gap_mask,
+                            &mut self.gap_mask_len,
+                            self.block.gaps >> first_pos,
+                            self.pos_in_block - first_pos,
+                        );
                     }
                     let return_pos = if flag_is_set(CONFIG, RETURN_DNA_CHUNK) {
                         self.global_pos()
@@ -386,6 +896,9 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                     if flag_is_not_set(CONFIG, SPLIT_NON_ACTG)
                         || ((1 << self.pos_in_block) & self.block.newline) != 0
                     {
+                        if flag_is_set(CONFIG, VALIDATE) {
+                            self.val_seq_len = self.global_pos() - self.val_line_start;
+                        }
                         self.consume_newline();
                     }
                     if flag_is_set(CONFIG, RETURN_DNA_CHUNK) {
@@ -394,19 +907,32 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                 }
                 2 => {
                     // PLUS
+                    if flag_is_set(CONFIG, VALIDATE)
+                        && !self.finished
+                        && self.lexer.input.current_chunk()[self.pos_in_block] != b'+'
+                    {
+                        self.validation_error = Some(ParseError::MissingPlus {
+                            line: self.line_count + 1,
+                        });
+                        self.finished = true;
+                        return None;
+                    }
                     while self.block.newline == 0 {
                         self.block = match self.lexer.next() {
                             Some(b) => b,
                             None => {
                                 self.finished = true;
+                                self.truncated = true;
                                 return None;
                             }
                         };
-                        self.block_counter += 1;
                         self.pos_in_block = 0;
                     }
                     self.pos_in_block = self.block.newline.trailing_zeros() as usize;
                     self.consume_newline();
+                    if flag_is_set(CONFIG, VALIDATE) {
+                        self.val_line_start = self.global_pos();
+                    }
                 }
                 3 => {
                     // QUALITY
@@ -424,15 +950,15 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                             Some(b) => b,
                             None => {
                                 self.finished = true;
+                                self.truncated = true;
                                 break; // return record
                             }
                         };
-                        self.block_counter += 1;
                         self.pos_in_block = 0;
                         first_pos = 0;
                     }
                     self.pos_in_block = self.block.newline.trailing_zeros() as usize;
-                    if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+                    if flag_is_set(CONFIG, COMPUTE_QUALITY) || flag_is_set(CONFIG, VALIDATE) {
                         self.pos_in_block =
                             self.pos_in_block.min(self.lexer.input.current_chunk_len());
                     }
@@ -447,6 +973,18 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
                             &self.lexer.input.current_chunk()[first_pos..self.pos_in_block];
                         self.cur_quality.extend_from_slice(quality_chunk);
                     }
+                    if flag_is_set(CONFIG, VALIDATE) {
+                        let quality_len = self.global_pos() - self.val_line_start;
+                        if quality_len != self.val_seq_len {
+                            self.validation_error = Some(ParseError::LengthMismatch {
+                                line: self.line_count + 1,
+                                seq_len: self.val_seq_len,
+                                quality_len,
+                            });
+                            self.finished = true;
+                            return None;
+                        }
+                    }
                     self.consume_newline();
                     if flag_is_set(CONFIG, RETURN_RECORD) {
                         return Some(Event::Record(self.global_pos()));
@@ -458,6 +996,67 @@ impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for FastqParser<'a, CO
     }
 }
 
+/// A [`FastqParser`] checking the 4-line invariants of the format: each record
+/// starts with `@`, its third line starts with `+`, and the sequence and
+/// quality lines have the same length.
+/// Iteration stops at the first violation, which is yielded as a final `Err`
+/// item; this requires the [`validate`](crate::ParserOptions::validate) flag.
+pub struct ValidatedFastqParser<'a, const CONFIG: Config, I: InputData<'a>> {
+    parser: FastqParser<'a, CONFIG, I>,
+    errored: bool,
+}
+
+impl<'a, const CONFIG: Config, I: InputData<'a>> FromInputData<'a, I>
+    for ValidatedFastqParser<'a, CONFIG, I>
+{
+    fn from_input(input: I) -> Self {
+        assert!(flag_is_set(CONFIG, VALIDATE));
+        Self {
+            parser: FastqParser::from_input(input),
+            errored: false,
+        }
+    }
+}
+
+impl<'a, const CONFIG: Config, I: InputData<'a>> core::ops::Deref
+    for ValidatedFastqParser<'a, CONFIG, I>
+{
+    type Target = FastqParser<'a, CONFIG, I>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.parser
+    }
+}
+
+impl<'a, const CONFIG: Config, I: InputData<'a>> core::ops::DerefMut
+    for ValidatedFastqParser<'a, CONFIG, I>
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.parser
+    }
+}
+
+impl<'a, const CONFIG: Config, I: InputData<'a>> Iterator for ValidatedFastqParser<'a, CONFIG, I> {
+    type Item = Result<Event, ParseError>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.errored {
+            return None;
+        }
+        match self.parser.next() {
+            Some(event) => Some(Ok(event)),
+            None => {
+                let error = self.parser.validation_error.take();
+                self.errored = error.is_some();
+                error.map(Err)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -523,6 +1122,53 @@ mod tests {
         assert_eq!(res, vec!["head", "hhh", "A B C "]);
     }
 
+    #[test]
+    fn test_header_input_modes() {
+        // every input mode must yield exactly the header text after '@'
+        fn collect<'a, I: InputData<'a>>(mut f: FastqParser<'a, CONFIG_HEADER, I>) -> Vec<Vec<u8>> {
+            let mut res = Vec::new();
+            while f.next().is_some() {
+                res.push(f.get_header_owned());
+            }
+            res
+        }
+
+        let expected = collect(FastqParser::from_slice(FASTQ));
+        assert_eq!(
+            expected,
+            vec![b"head".to_vec(), b"hhh".to_vec(), b"A B C ".to_vec()]
+        );
+        assert_eq!(collect(FastqParser::from_reader(FASTQ)), expected);
+
+        let path = std::env::temp_dir().join("helicase_test_header_modes.fastq");
+        std::fs::write(&path, FASTQ).unwrap();
+        assert_eq!(
+            collect(FastqParser::from_file_mmap(&path).unwrap()),
+            expected
+        );
+        assert_eq!(
+            collect(FastqParser::from_file_in_ram(&path).unwrap()),
+            expected
+        );
+        assert_eq!(collect(FastqParser::from_file(&path).unwrap()), expected);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_stats() {
+        let f = FastqParser::<CONFIG_STRING, _>::from_slice(FASTQ);
+        assert_eq!(
+            f.stats(),
+            FastxStats {
+                records: 3,
+                total_bases: 48,
+                n_bases: 4,
+                gc_bases: 11,
+                lines: 12,
+            }
+        );
+    }
+
     #[test]
     fn test_quality() {
         let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(FASTQ);
@@ -538,6 +1184,123 @@ mod tests {
         assert_eq!(res, vec!["123", "QQ@@++AA", "QUAL"]);
     }
 
+    #[test]
+    fn test_quality_summary() {
+        // long enough to exercise the vectorized strides plus a remainder
+        let quality: Vec<u8> = (0..300).map(|i| 33 + (i * 7 % 60) as u8).collect();
+        let mut fastq = b"@r\n".to_vec();
+        fastq.extend(std::iter::repeat_n(b'A', 300));
+        fastq.extend_from_slice(b"\n+\n");
+        fastq.extend_from_slice(&quality);
+        fastq.push(b'\n');
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(&fastq);
+        assert!(f.next().is_some());
+        let summary = f.quality_summary().unwrap();
+        // the SIMD path must agree with a scalar scan
+        assert_eq!(summary.min, quality.iter().copied().min().unwrap());
+        assert_eq!(summary.max, quality.iter().copied().max().unwrap());
+        assert_eq!(summary.sum, quality.iter().map(|&q| q as u64).sum::<u64>());
+        assert_eq!(summary.len, 300);
+        assert!((summary.mean(33) - (summary.sum as f64 / 300.0 - 33.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_buffer_pool() {
+        let mut fastq = Vec::new();
+        for i in 0..1000 {
+            fastq.extend_from_slice(
+                format!("@r{i}\nACGTACGTACGTACGT\n+\nIIIIIIIIIIIIIIII\n").as_bytes(),
+            );
+        }
+        let mut pool = BufferPool::with_capacity(2);
+        let mut f = FastqParser::<CONFIG_STRING, _>::from_reader(fastq.as_slice());
+        let mut warm_capacity = 0;
+        let mut count = 0;
+        while f.next().is_some() {
+            let buf = f.get_dna_string_pooled(&mut pool);
+            assert_eq!(buf, b"ACGTACGTACGTACGT");
+            if count == 0 {
+                warm_capacity = buf.capacity();
+            } else {
+                // after warmup the recycled buffer never regrows
+                assert_eq!(buf.capacity(), warm_capacity);
+            }
+            pool.release(buf);
+            count += 1;
+        }
+        assert_eq!(count, 1000);
+    }
+
+    #[test]
+    fn test_trimmed_quality_range() {
+        const CONFIG: Config = ParserOptions::default()
+            .dna_string()
+            .compute_quality()
+            .config();
+        // 'I' = Q40, '#' = Q2 with offset 33
+        let fastq = b"@r\nACGTACGT\n+\nIIIII###";
+        let mut f = FastqParser::<CONFIG, _>::from_slice(fastq);
+        f.next().unwrap();
+        let range = f.trimmed_quality_range(33, 3, 10);
+        assert_eq!(range, 0..5);
+        assert_eq!(&f.get_dna_string()[range], b"ACGTA");
+
+        // all windows above the threshold keeps the whole read
+        assert_eq!(f.trimmed_quality_range(33, 3, 1), 0..8);
+        // a failing first window trims everything
+        assert_eq!(f.trimmed_quality_range(33, 8, 40), 0..0);
+    }
+
+    #[test]
+    fn test_validate() {
+        const CONFIG_VALIDATE: Config = ParserOptions::default()
+            .compute_quality()
+            .validate()
+            .config();
+
+        // well-formed input yields only Ok events
+        let mut f = ValidatedFastqParser::<CONFIG_VALIDATE, _>::from_slice(
+            b"@r1\nACGT\n+\nIIII\n@r2\nTTTT\n+\n!!!!",
+        );
+        assert!(matches!(f.next(), Some(Ok(Event::Record(_)))));
+        assert_eq!(f.get_quality(), Some(b"IIII".as_slice()));
+        assert!(matches!(f.next(), Some(Ok(Event::Record(_)))));
+        assert!(f.next().is_none());
+
+        // a record not starting with '@'
+        let mut f = ValidatedFastqParser::<CONFIG_VALIDATE, _>::from_slice(b"r1\nACGT\n+\nIIII");
+        assert!(matches!(
+            f.next(),
+            Some(Err(ParseError::MissingAt { line: 1 }))
+        ));
+        assert!(f.next().is_none());
+
+        // a missing '+' line
+        let mut f = ValidatedFastqParser::<CONFIG_VALIDATE, _>::from_slice(
+            b"@r1\nACGT\nIIII\n@r2\nTTTT\n+\n!!!!",
+        );
+        assert!(matches!(
+            f.next(),
+            Some(Err(ParseError::MissingPlus { line: 3 }))
+        ));
+        assert!(f.next().is_none());
+
+        // a quality line shorter than the sequence
+        let mut f = ValidatedFastqParser::<CONFIG_VALIDATE, _>::from_slice(
+            b"@r1\nACGT\n+\nIIII\n@r2\nTTTT\n+\n!!!",
+        );
+        assert!(matches!(f.next(), Some(Ok(Event::Record(_)))));
+        assert!(matches!(
+            f.next(),
+            Some(Err(ParseError::LengthMismatch {
+                line: 8,
+                seq_len: 4,
+                quality_len: 3,
+            }))
+        ));
+        assert!(f.next().is_none());
+    }
+
     #[test]
     fn test_dna_string() {
         let mut f = FastqParser::<CONFIG_STRING, _>::from_slice(FASTQ);
@@ -642,4 +1405,256 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_record_offsets() {
+        // a reader returning short reads mid-stream produces buffers that are
+        // not multiples of 64 bytes, so offsets cannot be derived from the
+        // number of chunks alone
+        struct ShortReader<'a>(&'a [u8]);
+        impl std::io::Read for ShortReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = self.0.len().min(buf.len()).min(37);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        fn offsets(events: impl Iterator<Item = Event>) -> Vec<usize> {
+            events
+                .map(|e| match e {
+                    Event::Record(pos) | Event::DnaChunk(pos) => pos,
+                    Event::Kmer(_) => unreachable!(),
+                })
+                .collect()
+        }
+
+        let mut data = Vec::new();
+        for i in 0..300 {
+            data.extend_from_slice(format!("@r{i}\nACGTACGTAC\n+\nIIIIIIIIII\n").as_bytes());
+        }
+
+        let expected = offsets(FastqParser::<CONFIG_HEADER, _>::from_slice(&data));
+        assert_eq!(expected.len(), 300);
+        assert!(expected.windows(2).all(|w| w[0] < w[1]));
+
+        let reader = FastqParser::<CONFIG_HEADER, _>::from_reader(ShortReader(&data));
+        assert_eq!(offsets(reader), expected);
+    }
+
+    #[test]
+    fn test_from_input_at_boundary() {
+        let mut f =
+            FastqParser::<DEFAULT_CONFIG, _>::from_input_at_boundary(SliceInput::new(FASTQ))
+                .unwrap();
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"head");
+
+        // a quality-line fragment that happens to begin with `@`
+        let fragment = b"@III+FFFF\nACGT\n@next\nTTTT\n".as_slice();
+        let err =
+            FastqParser::<DEFAULT_CONFIG, _>::from_input_at_boundary(SliceInput::new(fragment))
+                .err()
+                .unwrap();
+        assert_eq!(err, ParseError::MissingPlus { line: 3 });
+
+        let fragment = b"IIII\n@next\nTTTT\n".as_slice();
+        let err =
+            FastqParser::<DEFAULT_CONFIG, _>::from_input_at_boundary(SliceInput::new(fragment))
+                .err()
+                .unwrap();
+        assert_eq!(err, ParseError::MissingAt { line: 1 });
+    }
+
+    #[test]
+    fn test_emit_kmers() {
+        const K: usize = 4;
+        const CONFIG_KMER: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .emit_kmers(K as u8)
+            .config();
+        let fastq = b"@r\nACGTTGCATTACGNGGAT\n+\nIIIIIIIIIIIIIIIIII\n";
+        let mut kmers = Vec::new();
+        let f = FastqParser::<CONFIG_KMER, _>::from_slice(fastq.as_slice());
+        for event in f {
+            if let Event::Kmer(value) = event {
+                kmers.push(value);
+            }
+        }
+        // the window resets at the `N`
+        let mut expected = Vec::new();
+        for span in ["ACGTTGCATTACG", "GGAT"] {
+            let mut packed = PackedDNA::new();
+            packed.push_str(span);
+            expected.extend(packed.kmers(K));
+        }
+        assert_eq!(kmers, expected);
+    }
+
+    #[test]
+    fn test_dna_len_only() {
+        const CONFIG_LEN: Config = ParserOptions::default().dna_len_only().config();
+        let mut f = FastqParser::<CONFIG_LEN, _>::from_slice(FASTQ);
+        let mut s = FastqParser::<DEFAULT_CONFIG, _>::from_slice(FASTQ);
+        while f.next().is_some() {
+            assert!(s.next().is_some());
+            assert_eq!(f.get_dna_len(), s.get_dna_string().len());
+        }
+    }
+
+    #[test]
+    fn test_base_qual_pairs() {
+        const CONFIG_QUALITY: Config = ParserOptions::default().compute_quality().config();
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(b"@r\nACGT\n+\n!I5#\n".as_slice());
+        assert!(f.next().is_some());
+        let pairs: Vec<(u8, u8)> = f.base_qual_pairs(33).unwrap().collect();
+        assert_eq!(pairs, [(b'A', 0), (b'C', 40), (b'G', 20), (b'T', 2)]);
+    }
+
+    #[test]
+    fn test_was_truncated() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().compute_quality().config();
+        // cut off in the middle of the quality line
+        let data = b"@r0\nACGT\n+\nIIII\n@r1\nACGT\n+\nII";
+        let mut f = FastqParser::<CONFIG_DEFAULT, _>::from_reader(data.as_slice());
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert!(!f.was_truncated());
+        assert!(matches!(f.next(), Some(Event::Record(_))));
+        assert_eq!(f.get_quality(), Some(b"II".as_slice()));
+        assert!(f.was_truncated());
+
+        // a complete file ends on a clean 4-line boundary
+        let data = b"@r0\nACGT\n+\nIIII\n";
+        let mut f = FastqParser::<CONFIG_DEFAULT, _>::from_reader(data.as_slice());
+        while f.next().is_some() {}
+        assert!(!f.was_truncated());
+    }
+
+    #[test]
+    fn test_current_line() {
+        const CONFIG_DEFAULT: Config = ParserOptions::default().config();
+        let data = b"@r0\nAAAA\n+\nIIII\n@r1\nCCCC\n+\nIIII\n@r2\nGGGG\n+\nIIII\n";
+        let mut f = FastqParser::<CONFIG_DEFAULT, _>::from_slice(data.as_slice());
+        for _ in 0..3 {
+            assert!(matches!(f.next(), Some(Event::Record(_))));
+        }
+        // the event leaves the parser past the record's quality line
+        assert_eq!(f.current_line(), 13);
+        // so the 4-line record started on line 9
+        assert_eq!(f.current_line() - 4, 9);
+    }
+
+    #[test]
+    fn test_phred_offset() {
+        const CONFIG_PHRED64: Config = ParserOptions::default()
+            .compute_quality()
+            .phred_offset(64)
+            .config();
+        let mut f = FastqParser::<CONFIG_PHRED64, _>::from_slice(b"@r\nACGT\n+\nhhff\n".as_slice());
+        assert!(f.next().is_some());
+        // `h` is 104 and `f` is 102, decoded against the configured offset
+        assert_eq!(f.get_quality_scores(), Some(vec![40, 40, 38, 38]));
+    }
+
+    #[test]
+    fn test_tolerate_blank_lines() {
+        const CONFIG_BLANK: Config = ParserOptions::default()
+            .compute_quality()
+            .tolerate_blank_lines()
+            .config();
+        let fastq = b"@r0\nACGT\n+\nIIII\n\n\n@r1\nTTTT\n+\nFFFF\n\n";
+        let mut f = FastqParser::<CONFIG_BLANK, _>::from_slice(fastq.as_slice());
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"r0");
+        assert_eq!(f.get_dna_string(), b"ACGT");
+        assert_eq!(f.get_quality(), Some(b"IIII".as_slice()));
+        assert!(f.next().is_some());
+        assert_eq!(f.get_header(), b"r1");
+        assert_eq!(f.get_dna_string(), b"TTTT");
+        assert_eq!(f.get_quality(), Some(b"FFFF".as_slice()));
+        assert!(f.next().is_none());
+    }
+
+    #[test]
+    fn test_peek_header() {
+        let fastq = b"@r0\nACGT\n+\nIIII\n@r1\nTTTT\n+\nIIII\n";
+        let mut f = FastqParser::<DEFAULT_CONFIG, _>::from_reader(fastq.as_slice());
+        assert_eq!(f.peek_header(), Some(b"r0".as_slice()));
+        // peeking again does not advance
+        assert_eq!(f.peek_header(), Some(b"r0".as_slice()));
+        // the peeked record is still surfaced by the iteration
+        let mut headers = Vec::new();
+        f.for_each_record(|r| headers.push(r.header().to_vec()));
+        assert_eq!(headers, [b"r0".to_vec(), b"r1".to_vec()]);
+    }
+
+    #[test]
+    fn test_expected_errors() {
+        const CONFIG_QUALITY: Config = ParserOptions::default().compute_quality().config();
+        // `!` is Q0 (p = 1) and `I` is Q40 (p = 1e-4)
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(b"@r\nACGT\n+\n!!II\n".as_slice());
+        assert!(f.next().is_some());
+        let errors = f.expected_errors(33).unwrap();
+        assert!((errors - 2.0002).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_batched_owned() {
+        const CONFIG_QUALITY: Config = ParserOptions::default().compute_quality().config();
+        let mut fastq = Vec::new();
+        for i in 0..10 {
+            fastq.extend_from_slice(format!("@r{i}\nACGT\n+\nIIII\n").as_bytes());
+        }
+        let f = FastqParser::<CONFIG_QUALITY, _>::from_reader(fastq.as_slice());
+        let batches: Vec<Vec<OwnedRecord>> = f.batched_owned(4).collect();
+        let sizes: Vec<usize> = batches.iter().map(|batch| batch.len()).collect();
+        assert_eq!(sizes, [4, 4, 2]);
+        for (i, record) in batches.iter().flatten().enumerate() {
+            assert_eq!(record.header, format!("r{i}").as_bytes());
+            assert_eq!(record.seq, b"ACGT");
+            assert_eq!(record.qual, Some(b"IIII".to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_detect_quality_offset() {
+        const CONFIG_QUALITY: Config = ParserOptions::default().compute_quality().config();
+        // `!` (33) is well below the phred64 range
+        let phred33 = b"@r\nACGT\n+\n!!II\n".as_slice();
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(phred33);
+        assert_eq!(f.detect_quality_offset(), Some(33));
+        // `h` (104) is well above the phred33 range
+        let phred64 = b"@r\nACGT\n+\nhhff\n".as_slice();
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(phred64);
+        assert_eq!(f.detect_quality_offset(), Some(64));
+        // `BCDEF` (66-70) fits both encodings
+        let ambiguous = b"@r\nACGTA\n+\nBCDEF\n".as_slice();
+        let mut f = FastqParser::<CONFIG_QUALITY, _>::from_slice(ambiguous);
+        assert_eq!(f.detect_quality_offset(), None);
+    }
+
+    #[test]
+    fn test_base_counts() {
+        const CONFIG_COUNTS: Config = ParserOptions::default()
+            .ignore_headers()
+            .ignore_dna()
+            .compute_base_counts()
+            .config();
+
+        // a record whose sequence fills exactly one 64-byte chunk
+        let mut fastq = b"@r\n".to_vec();
+        fastq.extend_from_slice(&[b'A'; 10]);
+        fastq.extend_from_slice(&[b'C'; 20]);
+        fastq.extend_from_slice(&[b'T'; 30]);
+        fastq.extend_from_slice(&[b'G'; 4]);
+        fastq.extend_from_slice(b"\n+\n");
+        fastq.extend_from_slice(&[b'I'; 64]);
+        fastq.push(b'\n');
+        let mut f = FastqParser::<CONFIG_COUNTS, _>::from_slice(&fastq);
+        assert!(f.next().is_some());
+        assert_eq!(f.get_base_counts(), [10, 20, 30, 4]);
+        assert!(f.next().is_none());
+    }
 }
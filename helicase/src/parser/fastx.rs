@@ -6,6 +6,20 @@ use crate::input::*;
 /// A wrapper for [`FastaParser`] / [`FastqParser`] detecting the format at runtime.
 pub struct FastxParser<'a, const CONFIG: Config>(Box<dyn ParserIter + 'a>);
 
+/// Name common binary formats from their magic bytes, so that pointing the
+/// parser at a BAM file says so instead of "unknown format".
+fn detect_binary(prefix: &[u8]) -> &'static str {
+    if prefix.starts_with(b"BAM\x01") {
+        "a BAM file"
+    } else if prefix.starts_with(b"CRAM") {
+        "a CRAM file"
+    } else if prefix.starts_with(&[0x1f, 0x8b]) {
+        "gzip-compressed data (was the `gz` feature compiled in?)"
+    } else {
+        "unrecognized data"
+    }
+}
+
 impl<'a, const CONFIG: Config, I: InputData<'a> + 'a> FromInputData<'a, I>
     for FastxParser<'a, CONFIG>
 {
@@ -13,7 +27,82 @@ impl<'a, const CONFIG: Config, I: InputData<'a> + 'a> FromInputData<'a, I>
         match input.first_byte() {
             b'>' => Self(Box::new(FastaParser::<CONFIG, I>::from_input(input))),
             b'@' => Self(Box::new(FastqParser::<CONFIG, I>::from_input(input))),
-            _ => panic!("Unknow input format"),
+            _ => panic!(
+                "Input is not FASTA/FASTQ: this looks like {}",
+                detect_binary(input.first_bytes(4))
+            ),
+        }
+    }
+}
+
+impl<'a, const CONFIG: Config> FastxParser<'a, CONFIG> {
+    /// Like [`from_input`](FromInputData::from_input), but a descriptive
+    /// [`ParseError::NotFastx`] instead of a panic when the content is not
+    /// FASTA/FASTQ, recognizing common binary magic (BAM, CRAM, gzip that
+    /// was not transparently decoded).
+    pub fn try_from_input<I: InputData<'a> + 'a>(input: I) -> Result<Self, ParseError> {
+        match input.first_byte() {
+            b'>' | b'@' => Ok(Self::from_input(input)),
+            _ => Err(ParseError::NotFastx {
+                detected: detect_binary(input.first_bytes(4)),
+            }),
+        }
+    }
+
+    /// Consume the parser, yielding one [`OwnedRecord`] per record,
+    /// e.g. to collect them into a `Vec` for later use.
+    /// Only the fields computed by the configuration are populated.
+    pub fn into_owned_records(self) -> impl Iterator<Item = OwnedRecord> + 'a {
+        self.records_with_offsets().map(|(record, _)| record)
+    }
+
+    /// Consume the parser, yielding one [`OwnedRecord`] per record together
+    /// with the byte offset just past it in the (decompressed) input.
+    /// Unlike the raw [`Event::Record`] payload, this is useful for reader
+    /// inputs too: the offset combines the buffer offset with the position in
+    /// the buffer, so a sidecar index can be built even for streamed or
+    /// compressed input.
+    pub fn records_with_offsets(mut self) -> impl Iterator<Item = (OwnedRecord, usize)> + 'a {
+        std::iter::from_fn(move || {
+            loop {
+                match self.next()? {
+                    Event::Record(pos) => {
+                        let record = OwnedRecord {
+                            header: if flag_is_set(CONFIG, COMPUTE_HEADER) {
+                                self.get_header_owned()
+                            } else {
+                                Vec::new()
+                            },
+                            seq: if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
+                                self.get_dna_string_owned()
+                            } else {
+                                Vec::new()
+                            },
+                            qual: if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+                                self.get_quality_owned()
+                            } else {
+                                None
+                            },
+                        };
+                        return Some((record, pos));
+                    }
+                    Event::DnaChunk(_) | Event::Kmer(_) => {}
+                }
+            }
+        })
+    }
+}
+
+impl<const CONFIG: Config> FastxParser<'static, CONFIG> {
+    /// Open `path` with a memory map when possible, falling back to buffered
+    /// reading when the mmap fails (e.g. network mounts, `/proc` files) or
+    /// maps no data.
+    /// The mmap path keeps the zero-copy accessors of a random-access input;
+    /// the fallback buffers the fields it computes, but parses identically.
+    pub fn from_path_auto<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        match MmapInput::new(&path) {
+            Ok(input) if !input.data().is_empty() => Ok(Self::from_input(input)),
+            _ => Ok(Self::from_input(FileInput::new(path)?)),
         }
     }
 }
@@ -99,6 +188,71 @@ impl<'a, const CONFIG: Config> Parser for FastxParser<'a, CONFIG> {
         assert!(flag_is_set(CONFIG, COMPUTE_DNA_LEN));
         self.0.get_dna_len()
     }
+
+    #[inline(always)]
+    fn get_actg_len(&self) -> usize {
+        self.0.get_actg_len()
+    }
+
+    #[inline(always)]
+    fn get_seq_hash(&self) -> u64 {
+        assert!(flag_is_set(CONFIG, COMPUTE_SEQ_HASH));
+        self.0.get_seq_hash()
+    }
+
+    #[inline(always)]
+    fn compression(&mut self) -> std::io::Result<Option<deko::Format>> {
+        self.0.compression()
+    }
+
+    #[inline(always)]
+    fn get_gap_mask(&self) -> &[u64] {
+        assert!(flag_is_set(CONFIG, COMPUTE_GAP_MASK));
+        self.0.get_gap_mask()
+    }
+
+    #[inline(always)]
+    fn try_get_header(&self) -> Option<&[u8]> {
+        self.0.try_get_header()
+    }
+
+    #[inline(always)]
+    fn try_get_dna_string(&self) -> Option<&[u8]> {
+        self.0.try_get_dna_string()
+    }
+
+    #[inline(always)]
+    fn try_get_quality(&self) -> Option<&[u8]> {
+        self.0.try_get_quality()
+    }
+
+    #[inline(always)]
+    fn current_line(&self) -> usize {
+        self.0.current_line()
+    }
+
+    #[inline(always)]
+    fn get_base_counts(&self) -> [usize; 4] {
+        assert!(flag_is_set(CONFIG, COMPUTE_BASE_COUNTS));
+        self.0.get_base_counts()
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn lexer_stats(&self) -> crate::lexer::LexerStats {
+        self.0.lexer_stats()
+    }
+
+    #[inline(always)]
+    fn record_bytes(&self) -> Option<&[u8]> {
+        self.0.record_bytes()
+    }
+
+    #[inline(always)]
+    fn line_width(&self) -> Option<usize> {
+        assert!(flag_is_set(CONFIG, COMPUTE_LINE_WIDTH));
+        self.0.line_width()
+    }
 }
 
 impl<'a, const CONFIG: Config> Iterator for FastxParser<'a, CONFIG> {
@@ -109,3 +263,101 @@ impl<'a, const CONFIG: Config> Iterator for FastxParser<'a, CONFIG> {
         self.0.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: Config = ParserOptions::default().compute_quality().config();
+
+    static FASTQ: &[u8] =
+        b"@head\nTTTCTtaAAAAAGAAAAACAAN\n+\n123\n@hhh\nCTCTTANNAAACAAAnAGCTTT\n+\nQQ@@++AA\n@A B C \nCCAC\n+\nQUAL"
+            .as_slice();
+
+    #[test]
+    fn test_into_owned_records() {
+        let f = FastxParser::<CONFIG>::from_slice(FASTQ);
+        let records: Vec<OwnedRecord> = f.into_owned_records().collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].header, b"head");
+        assert_eq!(records[0].seq, b"TTTCTtaAAAAAGAAAAACAAN");
+        assert_eq!(records[0].qual, Some(b"123".to_vec()));
+        assert_eq!(records[1].header, b"hhh");
+        assert_eq!(records[2].header, b"A B C ");
+        assert_eq!(records[2].seq, b"CCAC");
+        assert_eq!(records[2].qual, Some(b"QUAL".to_vec()));
+    }
+
+    #[test]
+    fn test_records_with_offsets() {
+        // span several read buffers to exercise the buffer offset tracking
+        let mut fastq = Vec::new();
+        for i in 0..4000 {
+            fastq.extend_from_slice(format!("@r{i}\nACGTACGTACGT\n+\nIIIIIIIIIIII\n").as_bytes());
+        }
+        let f = FastxParser::<CONFIG>::from_reader(fastq.as_slice());
+        let mut prev_offset = 0;
+        let mut count = 0;
+        for (record, offset) in f.records_with_offsets() {
+            assert_eq!(record.header, format!("r{count}").as_bytes());
+            assert_eq!(record.seq, b"ACGTACGTACGT");
+            assert_eq!(record.qual, Some(b"IIIIIIIIIIII".to_vec()));
+            assert!(offset > prev_offset);
+            prev_offset = offset;
+            count += 1;
+        }
+        assert_eq!(count, 4000);
+        // the offset of a FASTQ record points at the end of its quality line
+        assert_eq!(prev_offset, fastq.len() - 1);
+    }
+
+    #[test]
+    fn test_from_path_auto() {
+        let path = std::env::temp_dir().join("helicase_test_from_path_auto.fastq");
+        std::fs::write(&path, FASTQ).unwrap();
+        let f = FastxParser::<CONFIG>::from_path_auto(&path).unwrap();
+        let records: Vec<OwnedRecord> = f.into_owned_records().collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].header, b"head");
+        assert_eq!(records[2].qual, Some(b"QUAL".to_vec()));
+        std::fs::remove_file(&path).unwrap();
+
+        // a missing file is an error on both paths
+        assert!(FastxParser::<CONFIG>::from_path_auto(&path).is_err());
+    }
+
+    #[test]
+    fn test_try_from_input_binary_magic() {
+        let err = FastxParser::<CONFIG>::try_from_input(SliceInput::new(b"BAM\x01\x00\x00"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            ParseError::NotFastx {
+                detected: "a BAM file"
+            }
+        );
+        let err = FastxParser::<CONFIG>::try_from_input(SliceInput::new(b"CRAM\x03\x00"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            ParseError::NotFastx {
+                detected: "a CRAM file"
+            }
+        );
+        assert!(err.to_string().contains("CRAM"));
+        assert!(FastxParser::<CONFIG>::try_from_input(SliceInput::new(FASTQ)).is_ok());
+    }
+
+    #[test]
+    fn test_into_owned_records_respects_config() {
+        const CONFIG_HEADER: Config = ParserOptions::default().ignore_dna().config();
+        let f = FastxParser::<CONFIG_HEADER>::from_slice(FASTQ);
+        let records: Vec<OwnedRecord> = f.into_owned_records().collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].header, b"head");
+        assert!(records[0].seq.is_empty());
+        assert_eq!(records[0].qual, None);
+    }
+}
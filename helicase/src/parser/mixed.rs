@@ -0,0 +1,339 @@
+use super::*;
+use crate::config::{advanced::*, *};
+use crate::dna_format::*;
+use crate::input::*;
+
+/// A parser for files mixing FASTA and FASTQ records, e.g. concatenated
+/// outputs of different tools.
+///
+/// At each record boundary the leading byte (`>` vs `@`) selects the format,
+/// the record is delimited with a scalar scan, and the matching sub-parser is
+/// [reset](FastaParser::reset) on that slice, so buffers are reused across
+/// records. Only [`Record`](Event::Record) events are forwarded, with the
+/// absolute end offset of the record.
+/// This needs the whole input in memory, so it is built from a slice.
+pub struct MixedParser<'a, const CONFIG: Config> {
+    fasta: FastaParser<'a, CONFIG, SliceInput<'a>>,
+    fastq: FastqParser<'a, CONFIG, SliceInput<'a>>,
+    data: &'a [u8],
+    offset: usize,
+    record_start: usize,
+    format: Format,
+}
+
+impl<'a, const CONFIG: Config> MixedParser<'a, CONFIG> {
+    pub fn from_slice(data: &'a [u8]) -> Self {
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        Self {
+            // placeholder inputs, replaced by `reset` at the first boundary
+            fasta: FastaParser::from_slice(b"\n"),
+            fastq: FastqParser::from_slice(b"\n"),
+            data,
+            offset: 0,
+            record_start: 0,
+            format: Format::Fasta,
+        }
+    }
+
+    /// End of the 4-line FASTQ record starting at `start`.
+    fn end_of_fastq_record(&self, start: usize) -> usize {
+        let mut pos = start;
+        for _ in 0..4 {
+            match self.data[pos..].iter().position(|&byte| byte == b'\n') {
+                Some(newline) => pos += newline + 1,
+                None => return self.data.len(),
+            }
+        }
+        pos
+    }
+
+    /// End of the FASTA record starting at `start`: the first line starting
+    /// with the header byte or `@`.
+    fn end_of_fasta_record(&self, start: usize) -> usize {
+        let mut pos = start;
+        loop {
+            match self.data[pos..].iter().position(|&byte| byte == b'\n') {
+                Some(newline) => pos += newline + 1,
+                None => return self.data.len(),
+            }
+            match self.data.get(pos) {
+                Some(&byte) if byte == header_byte(CONFIG) || byte == b'@' => return pos,
+                Some(_) => {}
+                None => return self.data.len(),
+            }
+        }
+    }
+}
+
+impl<'a, const CONFIG: Config> Iterator for MixedParser<'a, CONFIG> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // skip blank lines between records
+            while self.offset < self.data.len()
+                && matches!(self.data[self.offset], b'\n' | b'\r' | b' ' | b'\t')
+            {
+                self.offset += 1;
+            }
+            if self.offset >= self.data.len() {
+                return None;
+            }
+            let start = self.offset;
+            self.record_start = start;
+            let (format, end) = if self.data[start] == b'@' {
+                (Format::Fastq, self.end_of_fastq_record(start))
+            } else {
+                (Format::Fasta, self.end_of_fasta_record(start))
+            };
+            self.format = format;
+            self.offset = end;
+            let record = SliceInput::new(&self.data[start..end]);
+            let found = match format {
+                Format::Fasta => {
+                    self.fasta.reset(record);
+                    self.fasta
+                        .by_ref()
+                        .any(|event| matches!(event, Event::Record(_)))
+                }
+                Format::Fastq => {
+                    self.fastq.reset(record);
+                    self.fastq
+                        .by_ref()
+                        .any(|event| matches!(event, Event::Record(_)))
+                }
+            };
+            if found {
+                return Some(Event::Record(end));
+            }
+            // a truncated record yields no event, move on to the next one
+        }
+    }
+}
+
+impl<'a, const CONFIG: Config> Parser for MixedParser<'a, CONFIG> {
+    #[inline(always)]
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    #[inline(always)]
+    fn clear_record(&mut self) {
+        self.fasta.clear_record();
+        self.fastq.clear_record();
+    }
+
+    #[inline(always)]
+    fn clear_chunk(&mut self) {
+        self.fasta.clear_chunk();
+        self.fastq.clear_chunk();
+    }
+
+    #[inline(always)]
+    fn get_header(&self) -> &[u8] {
+        match self.format {
+            Format::Fasta => self.fasta.get_header(),
+            Format::Fastq => self.fastq.get_header(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_header_owned(&mut self) -> Vec<u8> {
+        match self.format {
+            Format::Fasta => self.fasta.get_header_owned(),
+            Format::Fastq => self.fastq.get_header_owned(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_quality(&self) -> Option<&[u8]> {
+        match self.format {
+            Format::Fasta => None,
+            Format::Fastq => self.fastq.get_quality(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_quality_owned(&mut self) -> Option<Vec<u8>> {
+        match self.format {
+            Format::Fasta => None,
+            Format::Fastq => self.fastq.get_quality_owned(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_string(&self) -> &[u8] {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_string(),
+            Format::Fastq => self.fastq.get_dna_string(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_string_owned(&mut self) -> Vec<u8> {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_string_owned(),
+            Format::Fastq => self.fastq.get_dna_string_owned(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_columnar(&self) -> &ColumnarDNA {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_columnar(),
+            Format::Fastq => self.fastq.get_dna_columnar(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_columnar_owned(&mut self) -> ColumnarDNA {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_columnar_owned(),
+            Format::Fastq => self.fastq.get_dna_columnar_owned(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_packed(&self) -> &PackedDNA {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_packed(),
+            Format::Fastq => self.fastq.get_dna_packed(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_packed_owned(&mut self) -> PackedDNA {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_packed_owned(),
+            Format::Fastq => self.fastq.get_dna_packed_owned(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_dna_len(&self) -> usize {
+        match self.format {
+            Format::Fasta => self.fasta.get_dna_len(),
+            Format::Fastq => self.fastq.get_dna_len(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_actg_len(&self) -> usize {
+        match self.format {
+            Format::Fasta => self.fasta.get_actg_len(),
+            Format::Fastq => self.fastq.get_actg_len(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_seq_hash(&self) -> u64 {
+        match self.format {
+            Format::Fasta => self.fasta.get_seq_hash(),
+            Format::Fastq => self.fastq.get_seq_hash(),
+        }
+    }
+
+    #[inline(always)]
+    fn compression(&mut self) -> std::io::Result<Option<deko::Format>> {
+        match self.format {
+            Format::Fasta => self.fasta.compression(),
+            Format::Fastq => self.fastq.compression(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_gap_mask(&self) -> &[u64] {
+        match self.format {
+            Format::Fasta => self.fasta.get_gap_mask(),
+            Format::Fastq => self.fastq.get_gap_mask(),
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_header(&self) -> Option<&[u8]> {
+        match self.format {
+            Format::Fasta => self.fasta.try_get_header(),
+            Format::Fastq => self.fastq.try_get_header(),
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_dna_string(&self) -> Option<&[u8]> {
+        match self.format {
+            Format::Fasta => self.fasta.try_get_dna_string(),
+            Format::Fastq => self.fastq.try_get_dna_string(),
+        }
+    }
+
+    #[inline(always)]
+    fn try_get_quality(&self) -> Option<&[u8]> {
+        match self.format {
+            Format::Fasta => self.fasta.try_get_quality(),
+            Format::Fastq => self.fastq.try_get_quality(),
+        }
+    }
+
+    #[inline(always)]
+    fn current_line(&self) -> usize {
+        // lines before the current record, plus the sub-parser's position
+        // within its slice
+        let prefix = self.data[..self.record_start]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count();
+        prefix
+            + match self.format {
+                Format::Fasta => self.fasta.current_line(),
+                Format::Fastq => self.fastq.current_line(),
+            }
+    }
+
+    #[inline(always)]
+    fn get_base_counts(&self) -> [usize; 4] {
+        match self.format {
+            Format::Fasta => self.fasta.get_base_counts(),
+            Format::Fastq => self.fastq.get_base_counts(),
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    fn lexer_stats(&self) -> crate::lexer::LexerStats {
+        match self.format {
+            Format::Fasta => self.fasta.lexer_stats(),
+            Format::Fastq => self.fastq.lexer_stats(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: Config = ParserOptions::default().compute_quality().config();
+
+    #[test]
+    fn test_mixed_records() {
+        let data = b">contig\nACGT\nTTAA\n@read\nCCGG\n+\nIIII\n>tail\nGGCC\n";
+        let mut parser = MixedParser::<CONFIG>::from_slice(data.as_slice());
+
+        assert!(matches!(parser.next(), Some(Event::Record(_))));
+        assert_eq!(parser.format(), Format::Fasta);
+        assert_eq!(parser.get_header(), b"contig");
+        assert_eq!(parser.get_dna_string(), b"ACGTTTAA");
+        assert_eq!(parser.get_quality(), None);
+
+        assert!(matches!(parser.next(), Some(Event::Record(_))));
+        assert_eq!(parser.format(), Format::Fastq);
+        assert_eq!(parser.get_header(), b"read");
+        assert_eq!(parser.get_dna_string(), b"CCGG");
+        assert_eq!(parser.get_quality(), Some(b"IIII".as_slice()));
+
+        assert!(matches!(parser.next(), Some(Event::Record(_))));
+        assert_eq!(parser.format(), Format::Fasta);
+        assert_eq!(parser.get_header(), b"tail");
+        assert_eq!(parser.get_dna_string(), b"GGCC");
+
+        assert!(parser.next().is_none());
+    }
+}
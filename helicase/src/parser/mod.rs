@@ -3,19 +3,340 @@
 mod fasta;
 mod fastq;
 mod fastx;
+mod mixed;
 mod traits;
 
 pub use fasta::*;
 pub use fastq::*;
 pub use fastx::*;
+pub use mixed::*;
 pub use traits::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
     Fasta,
     Fastq,
 }
 
+/// An event emitted by a parser, carrying the byte offset just past the record
+/// or chunk, counted from the start of the (decompressed) input.
+/// For random-access inputs this is an index into the underlying data;
+/// for reader inputs it is an offset into the decompressed stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Event {
     Record(usize),
     DnaChunk(usize),
+    /// A 2-bit packed k-mer, emitted under the
+    /// [`emit_kmers`](crate::ParserOptions::emit_kmers) mode with the first
+    /// base in the low bits.
+    Kmer(u64),
+}
+
+/// A well-formedness violation detected under the
+/// [`validate`](crate::ParserOptions::validate) flag.
+/// Line numbers are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The record does not start with `@`.
+    MissingAt { line: usize },
+    /// The third line of the record does not start with `+`.
+    MissingPlus { line: usize },
+    /// The sequence and quality lines have different lengths.
+    LengthMismatch {
+        line: usize,
+        seq_len: usize,
+        quality_len: usize,
+    },
+    /// The input is not FASTA/FASTQ at all; `detected` names the binary
+    /// format recognized from its magic bytes, e.g. "a BAM file".
+    NotFastx { detected: &'static str },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingAt { line } => {
+                write!(f, "line {line}: record does not start with '@'")
+            }
+            Self::MissingPlus { line } => {
+                write!(f, "line {line}: separator line does not start with '+'")
+            }
+            Self::LengthMismatch {
+                line,
+                seq_len,
+                quality_len,
+            } => write!(
+                f,
+                "line {line}: quality length {quality_len} does not match sequence length {seq_len}"
+            ),
+            Self::NotFastx { detected } => {
+                write!(f, "input is not FASTA/FASTQ: this looks like {detected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An owned record, detached from the parser's buffers.
+/// Fields that are not computed by the configuration are left empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OwnedRecord {
+    pub header: Vec<u8>,
+    pub seq: Vec<u8>,
+    pub qual: Option<Vec<u8>>,
+}
+
+/// A small free-list of `Vec<u8>` buffers for workloads that keep every
+/// record: hand buffers out with [`acquire`](Self::acquire) (or
+/// [`Parser::get_dna_string_pooled`]) and give them back with
+/// [`release`](Self::release), so that steady-state parsing allocates
+/// nothing once the buffers have grown to the record size.
+pub struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+    max: usize,
+}
+
+impl BufferPool {
+    /// Create a pool retaining at most `max` released buffers; buffers
+    /// released beyond that are dropped.
+    /// `max` should match the number of records in flight at once.
+    #[inline(always)]
+    pub fn with_capacity(max: usize) -> Self {
+        Self {
+            buffers: Vec::with_capacity(max),
+            max,
+        }
+    }
+
+    /// Take a buffer out of the pool, or a fresh empty one when the pool is
+    /// dry (e.g. before the first [`release`](Self::release)).
+    #[inline(always)]
+    pub fn acquire(&mut self) -> Vec<u8> {
+        self.buffers.pop().unwrap_or_default()
+    }
+
+    /// Return a buffer to the pool for reuse, clearing its content but
+    /// keeping its capacity.
+    #[inline(always)]
+    pub fn release(&mut self, mut buf: Vec<u8>) {
+        if self.buffers.len() < self.max {
+            buf.clear();
+            self.buffers.push(buf);
+        }
+    }
+}
+
+/// A view of the current record, borrowing the parser so that its slices
+/// cannot outlive the record they belong to.
+///
+/// `Iterator` cannot yield items that borrow the iterator itself (that would
+/// require a lending iterator), which is why the `Iterator<Item = Event>` API
+/// relies on calling `get_*` at the right time. [`ParserIter::for_each_record`]
+/// hands a `RecordRef` to a closure instead, so misuse is rejected at compile
+/// time:
+/// ```compile_fail
+/// use helicase::*;
+///
+/// const CONFIG: Config = ParserOptions::default().config();
+/// let mut parser = FastaParser::<CONFIG, _>::from_slice(b">h\nACGT");
+/// let mut escaped: &[u8] = b"";
+/// // the borrow cannot escape the closure
+/// parser.for_each_record(|r| escaped = r.header());
+/// ```
+pub struct RecordRef<'a, P: Parser + ?Sized> {
+    pub(crate) parser: &'a P,
+}
+
+impl<'a, P: Parser + ?Sized> RecordRef<'a, P> {
+    /// Get a reference to the record's header.
+    #[inline(always)]
+    pub fn header(&self) -> &'a [u8] {
+        self.parser.get_header()
+    }
+
+    /// Get a reference to the record's sequence as a slice of bytes.
+    #[inline(always)]
+    pub fn seq(&self) -> &'a [u8] {
+        self.parser.get_dna_string()
+    }
+
+    /// Get a reference to the record's quality line.
+    /// This returns `None` for FASTA file.
+    #[inline(always)]
+    pub fn qual(&self) -> Option<&'a [u8]> {
+        self.parser.get_quality()
+    }
+}
+
+/// Accumulate A/C/T/G counts from `size` bits of the columnar bit lanes,
+/// indexing `counts` by the 2-bit base code.
+#[inline(always)]
+pub(crate) fn add_base_counts(counts: &mut [usize; 4], high_bit: u64, low_bit: u64, size: usize) {
+    if size == 0 {
+        return;
+    }
+    let mask = if size == 64 { !0 } else { (1 << size) - 1 };
+    let c = (!high_bit & low_bit & mask).count_ones() as usize;
+    let t = (high_bit & !low_bit & mask).count_ones() as usize;
+    let g = (high_bit & low_bit & mask).count_ones() as usize;
+    counts[0] += size - c - t - g;
+    counts[1] += c;
+    counts[2] += t;
+    counts[3] += g;
+}
+
+/// Fold `bytes` into an FxHash-style rolling hash, one byte at a time, so
+/// that chunk boundaries do not change the result.
+#[inline(always)]
+pub(crate) fn fold_seq_hash(hash: &mut u64, bytes: &[u8]) {
+    for &byte in bytes {
+        *hash = (hash.rotate_left(5) ^ byte as u64).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+
+/// Feed `num_bases` 2-bit codes into a rolling k-mer window, queueing each
+/// complete k-mer with the first base in the low bits, as in
+/// [`PackedDNA::kmers`](crate::dna_format::PackedDNA::kmers).
+#[inline(always)]
+pub(crate) fn feed_kmers(
+    queue: &mut std::collections::VecDeque<u64>,
+    val: &mut u64,
+    filled: &mut usize,
+    k: usize,
+    mut two_bits: u128,
+    num_bases: usize,
+) {
+    for _ in 0..num_bases {
+        *val = (*val >> 2) | (((two_bits & 0b11) as u64) << (2 * (k - 1)));
+        two_bits >>= 2;
+        *filled += 1;
+        if *filled >= k {
+            queue.push_back(*val);
+        }
+    }
+}
+
+/// Append `size` bits to a `Vec<u64>`-backed bitmask of length `len` bits.
+#[inline(always)]
+pub(crate) fn append_bit_mask(mask: &mut Vec<u64>, len: &mut usize, bits: u64, size: usize) {
+    if size == 0 {
+        return;
+    }
+    let bits = if size == 64 {
+        bits
+    } else {
+        bits & ((1 << size) - 1)
+    };
+    let rem = *len % 64;
+    if rem == 0 {
+        mask.push(bits);
+    } else {
+        *mask.last_mut().unwrap() |= bits << rem;
+        if size > 64 - rem {
+            mask.push(bits >> (64 - rem));
+        }
+    }
+    *len += size;
+}
+
+/// A one-pass summary of a quality line, computed over the raw quality bytes
+/// (no Phred offset applied) by the SIMD backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QualitySummary {
+    /// Lowest quality byte, `u8::MAX` for an empty line.
+    pub min: u8,
+    /// Highest quality byte, `0` for an empty line.
+    pub max: u8,
+    /// Sum of the quality bytes.
+    pub sum: u64,
+    /// Length of the quality line.
+    pub len: usize,
+}
+
+impl QualitySummary {
+    /// The mean quality score after subtracting `offset`, or `0.0` for an
+    /// empty line.
+    #[inline(always)]
+    pub fn mean(&self, offset: u8) -> f64 {
+        if self.len == 0 {
+            return 0.0;
+        }
+        let offset = offset as u64 * self.len as u64;
+        self.sum.saturating_sub(offset) as f64 / self.len as f64
+    }
+}
+
+/// Whole-file statistics computed in a single pass.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FastxStats {
+    /// Number of records.
+    pub records: usize,
+    /// Number of sequence characters, including ambiguous bases.
+    pub total_bases: usize,
+    /// Number of non-ACTG sequence characters.
+    pub n_bases: usize,
+    /// Number of G/C bases.
+    pub gc_bases: usize,
+    /// Number of lines consumed.
+    pub lines: usize,
+}
+
+/// Sniff the [`Format`] of a file and its compression format, without
+/// constructing a parser.
+/// The first non-whitespace byte of the (decompressed) content decides:
+/// `>` is FASTA and `@` is FASTQ.
+pub fn sniff_format<P: AsRef<std::path::Path>>(
+    path: P,
+) -> std::io::Result<(Format, Option<deko::Format>)> {
+    use crate::input::InputData;
+    use std::io;
+
+    let mut input = crate::input::FileInput::new(path)?;
+    let compression = input.compression_format()?;
+    for chunk in input {
+        for &byte in chunk {
+            match byte {
+                b'>' => return Ok((Format::Fasta, compression)),
+                b'@' => return Ok((Format::Fastq, compression)),
+                b' ' | b'\t' | b'\r' | b'\n' => {}
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Unknown input format starting with byte {byte:#04x}"),
+                    ));
+                }
+            }
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Empty input"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_format() {
+        let path = std::env::temp_dir().join("helicase_test_sniff.fastq");
+        std::fs::write(&path, b"@r\nACGT\n+\nIIII\n").unwrap();
+        assert_eq!(sniff_format(&path).unwrap(), (Format::Fastq, None));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "gz")]
+    fn test_sniff_format_gz() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b">h\nACGT\n").unwrap();
+        let path = std::env::temp_dir().join("helicase_test_sniff.fasta.gz");
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+        assert_eq!(
+            sniff_format(&path).unwrap(),
+            (Format::Fasta, Some(deko::Format::Gz))
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -1,6 +1,9 @@
 use super::*;
 use crate::dna_format::*;
 
+use std::io::{self, Write};
+use std::ops::Range;
+
 pub trait Parser {
     /// Get the [`Format`] associated to this parser.
     fn format(&self) -> Format;
@@ -12,7 +15,19 @@ pub trait Parser {
     /// This will trigger a new allocation and a copy.
     fn get_header_owned(&mut self) -> Vec<u8>;
 
+    /// Get the current header with a leading `prefix` removed when present,
+    /// e.g. to canonicalize `lcl|` or `gi|` identifiers when merging headers
+    /// from multiple tools.
+    #[inline(always)]
+    fn get_header_stripped(&self, prefix: &[u8]) -> &[u8] {
+        let header = self.get_header();
+        header.strip_prefix(prefix).unwrap_or(header)
+    }
+
     /// Get a reference to the current sequence as a slice of bytes.
+    /// With [`RAW_SEQUENCE`](crate::config::advanced::RAW_SEQUENCE), multi-line FASTA
+    /// sequences on random-access inputs are returned as a zero-copy slice of the
+    /// input that still contains the line feeds.
     fn get_dna_string(&self) -> &[u8];
 
     /// Get an owned version of the current sequence as a `Vec<u8>`.
@@ -33,9 +48,63 @@ pub trait Parser {
     /// This will trigger a new allocation.
     fn get_dna_packed_owned(&mut self) -> PackedDNA;
 
-    /// Get the length of the current sequence.
+    /// Get the length of the current sequence: every sequence character,
+    /// including ambiguous bases, independently of
+    /// [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG).
     fn get_dna_len(&self) -> usize;
 
+    /// Get the number of ACTG bases of the current sequence, excluding the
+    /// ambiguous characters that [`get_dna_len`](#method.get_dna_len) counts.
+    /// This needs [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG),
+    /// which makes the scan test alphabet membership.
+    fn get_actg_len(&self) -> usize;
+
+    /// Get the gap mask of the current sequence, with one bit per sequence byte
+    /// marking `-`/`.` positions.
+    /// This is only aligned with [`get_dna_string`](#method.get_dna_string) when
+    /// [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG) is disabled.
+    fn get_gap_mask(&self) -> &[u64];
+
+    /// Get the A/C/T/G counts accumulated since the last
+    /// [`clear_chunk`](#method.clear_chunk), indexed by the 2-bit base code
+    /// (`A`, `C`, `T`, `G`).
+    /// Without [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG),
+    /// non-ACTG bases are counted under the base sharing their 2-bit code.
+    fn get_base_counts(&self) -> [usize; 4];
+
+    /// Get the hash of the current record's sequence, folded over the bytes
+    /// as they are scanned (line feeds excluded) and reset per record.
+    /// This is a fast FxHash-style hash meant for exact-duplicate detection,
+    /// not a cryptographic digest; under
+    /// [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG) only the
+    /// kept bases are hashed.
+    fn get_seq_hash(&self) -> u64;
+
+    /// The [`LexerStats`](crate::lexer::LexerStats) accumulated by the
+    /// underlying lexer, compiled in with the `stats` feature.
+    #[cfg(feature = "stats")]
+    fn lexer_stats(&self) -> crate::lexer::LexerStats;
+
+    /// Get the raw bytes of the current record, from the start of its header
+    /// line to just before the next record (or EOF), preserving line wrapping,
+    /// case and whitespace.
+    /// This returns `None` for reader inputs, which do not keep the input
+    /// around.
+    #[inline(always)]
+    fn record_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Get the sequence line width of the current FASTA record, or `None`
+    /// if the lines are uneven (the last line of a record may be shorter).
+    /// This requires [`COMPUTE_LINE_WIDTH`](crate::config::advanced::COMPUTE_LINE_WIDTH)
+    /// without [`SPLIT_NON_ACTG`](crate::config::advanced::SPLIT_NON_ACTG),
+    /// and returns `None` for FASTQ file, where sequences are single lines.
+    #[inline(always)]
+    fn line_width(&self) -> Option<usize> {
+        None
+    }
+
     /// Get a reference to the current quality line.
     /// This returns `None` for FASTA file.
     #[inline(always)]
@@ -51,6 +120,174 @@ pub trait Parser {
         None
     }
 
+    /// Summarize the current quality line in one pass over its bytes, using
+    /// the SIMD backend (`min`/`max`/sum-of-absolute-differences) when one is
+    /// compiled in.
+    /// This returns `None` for FASTA file.
+    #[inline(always)]
+    fn quality_summary(&self) -> Option<QualitySummary> {
+        let quality = self.get_quality()?;
+        let (min, max, sum) = crate::simd::byte_stats(quality);
+        Some(QualitySummary {
+            min,
+            max,
+            sum,
+            len: quality.len(),
+        })
+    }
+
+    /// Copy the current header into the caller's buffer, clearing it first.
+    /// This avoids a new allocation when the buffer is reused across records.
+    #[inline(always)]
+    fn get_header_into(&self, buf: &mut Vec<u8>) {
+        buf.clear();
+        buf.extend_from_slice(self.get_header());
+    }
+
+    /// Copy the current sequence into the caller's buffer, clearing it first.
+    /// This avoids a new allocation when the buffer is reused across records.
+    #[inline(always)]
+    fn get_dna_string_into(&self, buf: &mut Vec<u8>) {
+        buf.clear();
+        buf.extend_from_slice(self.get_dna_string());
+    }
+
+    /// Get an owned copy of the current sequence in a buffer recycled from
+    /// `pool`, so that keeping every record does not allocate once the pooled
+    /// buffers have grown to the record size.
+    /// Return the buffer with [`BufferPool::release`] when done with it.
+    #[inline(always)]
+    fn get_dna_string_pooled(&self, pool: &mut BufferPool) -> Vec<u8> {
+        let mut buf = pool.acquire();
+        self.get_dna_string_into(&mut buf);
+        buf
+    }
+
+    /// Copy the current quality line into the caller's buffer, clearing it first.
+    /// This returns `false` for FASTA file, leaving the buffer untouched.
+    #[inline(always)]
+    fn get_quality_into(&self, buf: &mut Vec<u8>) -> bool {
+        match self.get_quality() {
+            Some(quality) => {
+                buf.clear();
+                buf.extend_from_slice(quality);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Write the current record as FASTA, dropping the quality.
+    /// `wrap` is the sequence line width (`0` emits a single line).
+    /// This only reads the header and sequence, so converting FASTQ does not
+    /// need [`COMPUTE_QUALITY`](crate::config::advanced::COMPUTE_QUALITY).
+    #[inline(always)]
+    fn write_as_fasta<W: Write>(&self, writer: W, wrap: usize) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        let mut writer = crate::output::FastaWriter::with_wrap(writer, wrap);
+        writer.write_record(self.get_header(), self.get_dna_string())
+    }
+
+    /// Compute the sub-range of the current record to keep after sliding-window
+    /// quality trimming of the 3' end.
+    /// Scores are `quality byte - offset`; the read is cut at the start of the
+    /// first window of `window` bases whose mean score drops below `threshold`.
+    /// This returns an empty range for FASTA file, which has no quality.
+    #[inline(always)]
+    fn trimmed_quality_range(&self, offset: u8, window: usize, threshold: u8) -> Range<usize> {
+        assert!(window >= 1);
+        let quality = match self.get_quality() {
+            Some(quality) => quality,
+            None => return 0..0,
+        };
+        let len = quality.len();
+        if len < window {
+            return 0..len;
+        }
+        let score = |q: u8| q.saturating_sub(offset) as usize;
+        let min_sum = threshold as usize * window;
+        let mut sum: usize = quality[..window].iter().map(|&q| score(q)).sum();
+        if sum < min_sum {
+            return 0..0;
+        }
+        for i in window..len {
+            sum += score(quality[i]);
+            sum -= score(quality[i - window]);
+            if sum < min_sum {
+                return 0..i - window + 1;
+            }
+        }
+        0..len
+    }
+
+    /// Like [`get_header`](#method.get_header), but `None` instead of a
+    /// panic when [`COMPUTE_HEADER`](crate::config::advanced::COMPUTE_HEADER)
+    /// is not enabled, so generic code can probe what the configuration
+    /// provides.
+    fn try_get_header(&self) -> Option<&[u8]>;
+
+    /// Like [`get_dna_string`](#method.get_dna_string), but `None` instead of
+    /// a panic when [`COMPUTE_DNA_STRING`](crate::config::advanced::COMPUTE_DNA_STRING)
+    /// is not enabled.
+    fn try_get_dna_string(&self) -> Option<&[u8]>;
+
+    /// Like [`get_quality`](#method.get_quality), but `None` instead of a
+    /// panic when [`COMPUTE_QUALITY`](crate::config::advanced::COMPUTE_QUALITY)
+    /// is not enabled (FASTA records still return `None`).
+    fn try_get_quality(&self) -> Option<&[u8]>;
+
+    /// The 1-based line number of the line the parser is currently
+    /// positioned on, for reporting malformed records.
+    /// At a [`Record`](crate::Event::Record) event the parser sits just past
+    /// the record, so a 4-line FASTQ record started 4 lines earlier; a FASTA
+    /// parser sits on the next header (or past the end of the input).
+    fn current_line(&self) -> usize;
+
+    /// Compute the base-2 Shannon entropy of the current record from the
+    /// A/C/T/G counts, between 0 (homopolymer) and 2 (uniform).
+    /// This reuses the accumulation behind [`get_base_counts`](#method.get_base_counts)
+    /// instead of a second pass over the sequence, so it needs
+    /// [`COMPUTE_BASE_COUNTS`](crate::config::advanced::COMPUTE_BASE_COUNTS).
+    /// An empty sequence has zero entropy.
+    #[inline(always)]
+    fn sequence_entropy(&self) -> f64 {
+        let counts = self.get_base_counts();
+        let total: usize = counts.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let mut entropy = 0.0;
+        for &count in &counts {
+            if count > 0 {
+                let p = count as f64 / total as f64;
+                entropy -= p * p.log2();
+            }
+        }
+        entropy
+    }
+
+    /// Whether the [`sequence_entropy`](#method.sequence_entropy) of the
+    /// current record falls below `threshold` bits, flagging low-complexity
+    /// reads such as poly-A tails or microsatellites.
+    #[inline(always)]
+    fn is_low_complexity(&self, threshold: f64) -> bool {
+        self.sequence_entropy() < threshold
+    }
+
+    /// The compression format detected on the underlying input, e.g. to
+    /// report "reading xz-compressed FASTQ".
+    /// This is `None` for uncompressed content and for random-access inputs,
+    /// which are never compressed.
+    /// A format compiled out of `deko` is not detected: the parser would see
+    /// the raw compressed bytes, which
+    /// [`sniff_format`](crate::parser::sniff_format) rejects up front.
+    #[inline(always)]
+    fn compression(&mut self) -> io::Result<Option<deko::Format>> {
+        Ok(None)
+    }
+
     /// Clear the information of the current record.
     /// This is only useful when [`MERGE_DNA_CHUNKS`](crate::config::advanced::MERGE_DNA_CHUNKS) is enabled.
     fn clear_chunk(&mut self);
@@ -60,6 +297,90 @@ pub trait Parser {
     fn clear_record(&mut self);
 }
 
-pub trait ParserIter: Parser + Iterator<Item = Event> {}
+pub trait ParserIter: Parser + Iterator<Item = Event> {
+    /// Call `f` with a [`RecordRef`] for each remaining record.
+    /// The borrow in `RecordRef` prevents reading a record after the parser
+    /// advanced past it; see [`RecordRef`] for details.
+    /// ```
+    /// use helicase::input::*;
+    /// use helicase::parser::ParserIter;
+    /// use helicase::*;
+    ///
+    /// const CONFIG: Config = ParserOptions::default().config();
+    /// let mut parser = FastaParser::<CONFIG, _>::from_slice(b">a\nAC\n>b\nGT");
+    /// let mut headers = Vec::new();
+    /// parser.for_each_record(|r| headers.push(r.header().to_vec()));
+    /// assert_eq!(headers, [b"a", b"b"]);
+    /// ```
+    #[inline(always)]
+    fn for_each_record<F: FnMut(RecordRef<'_, Self>)>(&mut self, mut f: F)
+    where
+        Self: Sized,
+    {
+        while let Some(event) = self.next() {
+            if let Event::Record(_) = event {
+                f(RecordRef { parser: self });
+            }
+        }
+    }
+
+    /// Call `f(header, seq, qual)` for each remaining record, with slices
+    /// valid for that call only.
+    /// This is [`for_each_record`](Self::for_each_record) without the
+    /// [`RecordRef`] indirection, for closures that only want the raw fields.
+    /// The quality is `None` for FASTA file.
+    #[inline(always)]
+    fn for_each_record_fields<F: FnMut(&[u8], &[u8], Option<&[u8]>)>(&mut self, mut f: F)
+    where
+        Self: Sized,
+    {
+        while let Some(event) = self.next() {
+            if let Event::Record(_) = event {
+                f(self.get_header(), self.get_dna_string(), self.get_quality());
+            }
+        }
+    }
+
+    /// Consume the parser and collect the header of every remaining record,
+    /// e.g. to build a lookup table of IDs.
+    /// Pair this with a header-only configuration
+    /// ([`ignore_dna`](crate::ParserOptions::ignore_dna)) so that sequence
+    /// fields are not computed just to be discarded.
+    #[inline(always)]
+    fn collect_headers(mut self) -> Vec<Vec<u8>>
+    where
+        Self: Sized,
+    {
+        let mut headers = Vec::new();
+        while let Some(event) = self.next() {
+            if let Event::Record(_) = event {
+                headers.push(self.get_header_owned());
+            }
+        }
+        headers
+    }
+
+    /// Limit the iteration to the first `n` records, e.g. for a preview.
+    /// Unlike `take(n)`, this counts [`Record`](Event::Record) events only, so
+    /// the [`DnaChunk`](Event::DnaChunk) and [`Kmer`](Event::Kmer) events
+    /// belonging to those records are still surfaced.
+    #[inline(always)]
+    fn take_records(mut self, n: usize) -> impl Iterator<Item = Event>
+    where
+        Self: Sized,
+    {
+        let mut records = 0;
+        std::iter::from_fn(move || {
+            if records >= n {
+                return None;
+            }
+            let event = self.next()?;
+            if let Event::Record(_) = event {
+                records += 1;
+            }
+            Some(event)
+        })
+    }
+}
 
 impl<T: Parser + Iterator<Item = Event>> ParserIter for T {}
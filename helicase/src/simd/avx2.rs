@@ -5,9 +5,13 @@ use crate::lexer::*;
 use core::arch::x86_64::*;
 use core::mem::transmute;
 
-const GREATER_THAN: __m256i = unsafe { transmute([b'>'; 32]) };
+const SEMICOLON: __m256i = unsafe { transmute([b';'; 32]) };
 const LINE_FEED: __m256i = unsafe { transmute([b'\n'; 32]) };
+const DASH: __m256i = unsafe { transmute([b'-'; 32]) };
+const DOT: __m256i = unsafe { transmute([b'.'; 32]) };
 const LUT_ACTG: __m256i = unsafe { transmute(*b"A_C_T_G_________A_C_T_G_________") };
+const STAR: __m256i = unsafe { transmute([b'*'; 32]) };
+const LETTER_U: __m256i = unsafe { transmute([b'U'; 32]) };
 
 #[inline(always)]
 pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
@@ -16,32 +20,42 @@ pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
         let v_buf1 = _mm256_loadu_si256(ptr);
         let v_buf2 = _mm256_loadu_si256(ptr.add(1));
 
-        let open_bracket = u8_mask(v_buf1, v_buf2, GREATER_THAN);
+        // the separator is configurable, so the compared vector cannot be const
+        let header = _mm256_set1_epi8(header_byte(CONFIG) as i8);
+        let open_bracket = u8_mask(v_buf1, v_buf2, header);
         let line_feeds = u8_mask(v_buf1, v_buf2, LINE_FEED);
+        let semicolons = if flag_is_set(CONFIG, FASTA_COMMENTS) {
+            u8_mask(v_buf1, v_buf2, SEMICOLON)
+        } else {
+            0
+        };
 
         let mut is_dna = !0;
+        let mut gaps = 0;
         let mut two_bits = 0;
         let mut high_bit = 0;
         let mut low_bit = 0;
 
-        let (mm_hi_1, mm_lo_1, mm_hi_2, mm_lo_2) =
-            if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED) {
-                (
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 5)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 6)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 5)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 6)) as u32 as u64,
-                )
-            } else {
-                (0, 0, 0, 0)
-            };
-
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        let (mm_hi_1, mm_lo_1, mm_hi_2, mm_lo_2) = if flag_is_set(
+            CONFIG,
+            COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED | COMPUTE_BASE_COUNTS | KMER_MASK,
+        ) {
+            (
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 5)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 6)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 5)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 6)) as u32 as u64,
+            )
+        } else {
+            (0, 0, 0, 0)
+        };
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             high_bit = mm_hi_1 | (mm_hi_2 << 32);
             low_bit = mm_lo_1 | (mm_lo_2 << 32);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             let mm_1 =
                 _pdep_u64(mm_hi_1, 0xAAAAAAAAAAAAAAAA) | _pdep_u64(mm_lo_1, 0x5555555555555555);
             let mm_2 =
@@ -50,28 +64,25 @@ pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            is_dna = movemask_64(
-                _mm256_cmpeq_epi8(
-                    _mm256_shuffle_epi8(
-                        LUT_ACTG,
-                        _mm256_and_si256(v_buf1, _mm256_set1_epi8(0b110i8)),
-                    ),
-                    _mm256_and_si256(v_buf1, _mm256_set1_epi8(0b11011111u8 as i8)),
-                ),
-                _mm256_cmpeq_epi8(
-                    _mm256_shuffle_epi8(
-                        LUT_ACTG,
-                        _mm256_and_si256(v_buf2, _mm256_set1_epi8(0b110i8)),
-                    ),
-                    _mm256_and_si256(v_buf2, _mm256_set1_epi8(0b11011111u8 as i8)),
-                ),
-            );
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(protein_mask(v_buf1), protein_mask(v_buf2))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(rna_mask(v_buf1), rna_mask(v_buf2))
+            } else {
+                movemask_64(actg_mask(v_buf1), actg_mask(v_buf2))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = u8_mask(v_buf1, v_buf2, DASH) | u8_mask(v_buf1, v_buf2, DOT);
         }
 
         FastaBitmask {
             open_bracket,
+            semicolons,
             line_feeds,
             is_dna,
+            gaps,
             two_bits,
             high_bit,
             low_bit,
@@ -89,28 +100,31 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
         let line_feeds = u8_mask(v_buf1, v_buf2, LINE_FEED);
 
         let mut is_dna = !0;
+        let mut gaps = 0;
         let mut two_bits = 0;
         let mut high_bit = 0;
         let mut low_bit = 0;
 
-        let (mm_hi_1, mm_lo_1, mm_hi_2, mm_lo_2) =
-            if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED) {
-                (
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 5)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 6)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 5)) as u32 as u64,
-                    _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 6)) as u32 as u64,
-                )
-            } else {
-                (0, 0, 0, 0)
-            };
-
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        let (mm_hi_1, mm_lo_1, mm_hi_2, mm_lo_2) = if flag_is_set(
+            CONFIG,
+            COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED | COMPUTE_BASE_COUNTS | KMER_MASK,
+        ) {
+            (
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 5)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf1, 6)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 5)) as u32 as u64,
+                _mm256_movemask_epi8(_mm256_slli_epi16(v_buf2, 6)) as u32 as u64,
+            )
+        } else {
+            (0, 0, 0, 0)
+        };
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             high_bit = mm_hi_1 | (mm_hi_2 << 32);
             low_bit = mm_lo_1 | (mm_lo_2 << 32);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             let mm_1 =
                 _pdep_u64(mm_hi_1, 0xAAAAAAAAAAAAAAAA) | _pdep_u64(mm_lo_1, 0x5555555555555555);
             let mm_2 =
@@ -119,27 +133,23 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            is_dna = movemask_64(
-                _mm256_cmpeq_epi8(
-                    _mm256_shuffle_epi8(
-                        LUT_ACTG,
-                        _mm256_and_si256(v_buf1, _mm256_set1_epi8(0b110i8)),
-                    ),
-                    _mm256_and_si256(v_buf1, _mm256_set1_epi8(0b11011111u8 as i8)),
-                ),
-                _mm256_cmpeq_epi8(
-                    _mm256_shuffle_epi8(
-                        LUT_ACTG,
-                        _mm256_and_si256(v_buf2, _mm256_set1_epi8(0b110i8)),
-                    ),
-                    _mm256_and_si256(v_buf2, _mm256_set1_epi8(0b11011111u8 as i8)),
-                ),
-            );
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(protein_mask(v_buf1), protein_mask(v_buf2))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(rna_mask(v_buf1), rna_mask(v_buf2))
+            } else {
+                movemask_64(actg_mask(v_buf1), actg_mask(v_buf2))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = u8_mask(v_buf1, v_buf2, DASH) | u8_mask(v_buf1, v_buf2, DOT);
         }
 
         FastqBitmask {
             line_feeds,
             is_dna,
+            gaps,
             two_bits,
             high_bit,
             low_bit,
@@ -147,6 +157,41 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
     }
 }
 
+/// Membership mask for the ACTG alphabet of either case.
+#[inline(always)]
+fn actg_mask(v_buf: __m256i) -> __m256i {
+    unsafe {
+        _mm256_cmpeq_epi8(
+            _mm256_shuffle_epi8(LUT_ACTG, _mm256_and_si256(v_buf, _mm256_set1_epi8(0b110i8))),
+            _mm256_and_si256(v_buf, _mm256_set1_epi8(0b11011111u8 as i8)),
+        )
+    }
+}
+
+/// Membership mask for the RNA alphabet: ACTG plus `U` of either case,
+/// which shares T's 2-bit code in the packed formats.
+#[inline(always)]
+fn rna_mask(v_buf: __m256i) -> __m256i {
+    unsafe {
+        let uppercase = _mm256_and_si256(v_buf, _mm256_set1_epi8(0b11011111u8 as i8));
+        _mm256_or_si256(actg_mask(v_buf), _mm256_cmpeq_epi8(uppercase, LETTER_U))
+    }
+}
+
+/// Membership mask for the protein alphabet: the letters `A..=Z` of either
+/// case plus the `*` stop codon.
+#[inline(always)]
+fn protein_mask(v_buf: __m256i) -> __m256i {
+    unsafe {
+        let uppercase = _mm256_and_si256(v_buf, _mm256_set1_epi8(0b11011111u8 as i8));
+        let letters = _mm256_and_si256(
+            _mm256_cmpgt_epi8(uppercase, _mm256_set1_epi8((b'A' - 1) as i8)),
+            _mm256_cmpgt_epi8(_mm256_set1_epi8((b'Z' + 1) as i8), uppercase),
+        );
+        _mm256_or_si256(letters, _mm256_cmpeq_epi8(v_buf, STAR))
+    }
+}
+
 #[inline(always)]
 fn movemask_64(v1: __m256i, v2: __m256i) -> u64 {
     unsafe {
@@ -164,3 +209,34 @@ pub fn u8_mask(v_buf: __m256i, v_buf2: __m256i, v_c: __m256i) -> u64 {
         a | (b << 32)
     }
 }
+
+/// Compute `(min, max, sum)` over an arbitrary-length byte slice in 32-byte
+/// strides, e.g. to summarize a quality line.
+/// `min` is `u8::MAX` over an empty slice.
+#[inline(always)]
+pub fn byte_stats(buf: &[u8]) -> (u8, u8, u64) {
+    unsafe {
+        let mut v_min = _mm256_set1_epi8(-1);
+        let mut v_max = _mm256_setzero_si256();
+        let mut v_sum = _mm256_setzero_si256();
+        let mut chunks = buf.chunks_exact(32);
+        for chunk in &mut chunks {
+            let v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+            v_min = _mm256_min_epu8(v_min, v);
+            v_max = _mm256_max_epu8(v_max, v);
+            v_sum = _mm256_add_epi64(v_sum, _mm256_sad_epu8(v, _mm256_setzero_si256()));
+        }
+        let mins: [u8; 32] = transmute(v_min);
+        let maxs: [u8; 32] = transmute(v_max);
+        let sums: [u64; 4] = transmute(v_sum);
+        let mut min = mins.into_iter().min().unwrap();
+        let mut max = maxs.into_iter().max().unwrap();
+        let mut sum = sums.into_iter().sum();
+        for &x in chunks.remainder() {
+            min = min.min(x);
+            max = max.max(x);
+            sum += x as u64;
+        }
+        (min, max, sum)
+    }
+}
@@ -7,39 +7,59 @@ const LUT_ACTG: [u8; 16] = *b"A_C_T_G_________";
 #[inline(always)]
 pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
     let mut open_bracket = 0;
+    let mut semicolons = 0;
     let mut line_feeds = 0;
-    let mut is_dna = !0;
+    // without SPLIT_NON_ACTG the membership mask is unused and stays all-ones
+    let mut is_dna = if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
+        0
+    } else {
+        !0
+    };
+    let mut gaps = 0;
     let mut two_bits = 0;
     let mut high_bit = 0;
     let mut low_bit = 0;
 
     for (i, &x) in buf.iter().enumerate().take(64) {
         let bit = 1 << i;
-        open_bracket |= if x == b'>' { bit } else { 0 };
+        open_bracket |= if x == header_byte(CONFIG) { bit } else { 0 };
         line_feeds |= if x == b'\n' { bit } else { 0 };
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        if flag_is_set(CONFIG, FASTA_COMMENTS) {
+            semicolons |= if x == b';' { bit } else { 0 };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps |= if x == b'-' || x == b'.' { bit } else { 0 };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             high_bit |= ((x & 0b100) as u64) << i.wrapping_sub(2);
             low_bit |= ((x & 0b10) as u64) << i.wrapping_sub(1);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             two_bits |= ((x & 0b110) as u128) << (2 * i).wrapping_sub(1);
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            is_dna |= if (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize] {
-                bit
+            let member = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                x.is_ascii_alphabetic() || x == b'*'
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize] || (x & UPPERCASE) == b'U'
             } else {
-                0
+                (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize]
             };
+            is_dna |= if member { bit } else { 0 };
         }
     }
 
     FastaBitmask {
         open_bracket,
+        semicolons,
         line_feeds,
         is_dna,
+        gaps,
         two_bits,
         high_bit,
         low_bit,
@@ -49,7 +69,13 @@ pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
 #[inline(always)]
 pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
     let mut line_feeds = 0;
-    let mut is_dna = !0;
+    // without SPLIT_NON_ACTG the membership mask is unused and stays all-ones
+    let mut is_dna = if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
+        0
+    } else {
+        !0
+    };
+    let mut gaps = 0;
     let mut two_bits = 0;
     let mut high_bit = 0;
     let mut low_bit = 0;
@@ -58,21 +84,53 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
         let bit = 1 << i;
         line_feeds |= if x == b'\n' { bit } else { 0 };
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps |= if x == b'-' || x == b'.' { bit } else { 0 };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             high_bit |= ((x & 0b100) as u64) << i.wrapping_sub(2);
             low_bit |= ((x & 0b10) as u64) << i.wrapping_sub(1);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             two_bits |= ((x & 0b110) as u128) << (2 * i).wrapping_sub(1);
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            is_dna |= if (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize] {
-                bit
+            let member = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                x.is_ascii_alphabetic() || x == b'*'
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize] || (x & UPPERCASE) == b'U'
             } else {
-                0
+                (x & UPPERCASE) == LUT_ACTG[(x & 0b110) as usize]
             };
+            is_dna |= if member { bit } else { 0 };
         }
     }
+
+    FastqBitmask {
+        line_feeds,
+        is_dna,
+        gaps,
+        two_bits,
+        high_bit,
+        low_bit,
+    }
+}
+
+/// Compute `(min, max, sum)` over an arbitrary-length byte slice, e.g. to
+/// summarize a quality line.
+/// `min` is `u8::MAX` over an empty slice.
+#[inline(always)]
+pub fn byte_stats(buf: &[u8]) -> (u8, u8, u64) {
+    let mut min = u8::MAX;
+    let mut max = 0;
+    let mut sum = 0u64;
+    for &x in buf {
+        min = min.min(x);
+        max = max.max(x);
+        sum += x as u64;
+    }
+    (min, max, sum)
 }
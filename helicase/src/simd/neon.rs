@@ -5,11 +5,17 @@ use crate::lexer::*;
 use core::arch::aarch64::*;
 use core::mem::transmute;
 
-const GREATER_THAN: uint8x16_t = unsafe { transmute([b'>'; 16]) };
+const SEMICOLON: uint8x16_t = unsafe { transmute([b';'; 16]) };
 const LINE_FEED: uint8x16_t = unsafe { transmute([b'\n'; 16]) };
+const DASH: uint8x16_t = unsafe { transmute([b'-'; 16]) };
+const DOT: uint8x16_t = unsafe { transmute([b'.'; 16]) };
 const UPPERCASE: uint8x16_t = unsafe { transmute([0b11011111u8; 16]) };
 const TWO_BITS: uint8x16_t = unsafe { transmute([0b110u8; 16]) };
 const LUT_ACTG: uint8x16_t = unsafe { transmute(*b"A_C_T_G_________") };
+const LETTER_U: uint8x16_t = unsafe { transmute([b'U'; 16]) };
+const STAR: uint8x16_t = unsafe { transmute([b'*'; 16]) };
+const LETTER_A: uint8x16_t = unsafe { transmute([b'A'; 16]) };
+const LETTER_Z: uint8x16_t = unsafe { transmute([b'Z'; 16]) };
 
 #[inline(always)]
 pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
@@ -17,27 +23,38 @@ pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
         let ptr = buf.as_ptr();
         let v = vld4q_u8(ptr);
 
-        let open_bracket = movemask_64(map_8x16x4(v, |v| vceqq_u8(v, GREATER_THAN)));
+        // the separator is configurable, so the compared vector cannot be const
+        let header = vdupq_n_u8(header_byte(CONFIG));
+        let open_bracket = movemask_64(map_8x16x4(v, |v| vceqq_u8(v, header)));
         let line_feeds = movemask_64(map_8x16x4(v, |v| vceqq_u8(v, LINE_FEED)));
+        let semicolons = if flag_is_set(CONFIG, FASTA_COMMENTS) {
+            movemask_64(map_8x16x4(v, |v| vceqq_u8(v, SEMICOLON)))
+        } else {
+            0
+        };
 
         let mut is_dna = !0;
+        let mut gaps = 0;
         let mut two_bits = 0;
         let mut high_bit = 0;
         let mut low_bit = 0;
 
-        let shift_5 = if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED) {
+        let shift_5 = if flag_is_set(
+            CONFIG,
+            COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED | COMPUTE_BASE_COUNTS | KMER_MASK,
+        ) {
             map_8x16x4(v, |v| vshlq_n_u8::<5>(v))
         } else {
             v
         };
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             let shift_6 = map_8x16x4(v, |v| vshlq_n_u8::<6>(v));
             high_bit = movemask_64(shift_5);
             low_bit = movemask_64(shift_6);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             let bitpacked = vsriq_n_u8(
                 vsriq_n_u8(shift_5.3, shift_5.2, 2),
                 vsriq_n_u8(shift_5.1, shift_5.0, 2),
@@ -47,15 +64,27 @@ pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            let lookup = map_8x16x4(v, |v| vqtbl1q_u8(LUT_ACTG, vandq_u8(v, TWO_BITS)));
-            let uppercase = map_8x16x4(v, |v| vandq_u8(v, UPPERCASE));
-            is_dna = movemask_64(map_two_8x16x4(lookup, uppercase, |v1, v2| vceqq_u8(v1, v2)));
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(map_8x16x4(v, protein_mask))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(map_8x16x4(v, rna_mask))
+            } else {
+                movemask_64(map_8x16x4(v, actg_mask))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = movemask_64(map_8x16x4(v, |v| {
+                vorrq_u8(vceqq_u8(v, DASH), vceqq_u8(v, DOT))
+            }));
         }
 
         FastaBitmask {
             open_bracket,
+            semicolons,
             line_feeds,
             is_dna,
+            gaps,
             two_bits,
             high_bit,
             low_bit,
@@ -72,23 +101,27 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
         let line_feeds = movemask_64(map_8x16x4(v, |v| vceqq_u8(v, LINE_FEED)));
 
         let mut is_dna = !0;
+        let mut gaps = 0;
         let mut two_bits = 0;
         let mut high_bit = 0;
         let mut low_bit = 0;
 
-        let shift_5 = if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED) {
+        let shift_5 = if flag_is_set(
+            CONFIG,
+            COMPUTE_DNA_COLUMNAR | COMPUTE_DNA_PACKED | COMPUTE_BASE_COUNTS | KMER_MASK,
+        ) {
             map_8x16x4(v, |v| vshlq_n_u8::<5>(v))
         } else {
             v
         };
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
             let shift_6 = map_8x16x4(v, |v| vshlq_n_u8::<6>(v));
             high_bit = movemask_64(shift_5);
             low_bit = movemask_64(shift_6);
         }
 
-        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED) {
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
             let bitpacked = vsriq_n_u8(
                 vsriq_n_u8(shift_5.3, shift_5.2, 2),
                 vsriq_n_u8(shift_5.1, shift_5.0, 2),
@@ -98,14 +131,25 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
         }
 
         if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
-            let lookup = map_8x16x4(v, |v| vqtbl1q_u8(LUT_ACTG, vandq_u8(v, TWO_BITS)));
-            let uppercase = map_8x16x4(v, |v| vandq_u8(v, UPPERCASE));
-            is_dna = movemask_64(map_two_8x16x4(lookup, uppercase, |v1, v2| vceqq_u8(v1, v2)));
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(map_8x16x4(v, protein_mask))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(map_8x16x4(v, rna_mask))
+            } else {
+                movemask_64(map_8x16x4(v, actg_mask))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = movemask_64(map_8x16x4(v, |v| {
+                vorrq_u8(vceqq_u8(v, DASH), vceqq_u8(v, DOT))
+            }));
         }
 
         FastqBitmask {
             line_feeds,
             is_dna,
+            gaps,
             two_bits,
             high_bit,
             low_bit,
@@ -113,20 +157,42 @@ pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
     }
 }
 
+/// Membership mask for the ACTG alphabet of either case.
 #[inline(always)]
-fn map_8x16x4<F>(v: uint8x16x4_t, mut f: F) -> uint8x16x4_t
-where
-    F: FnMut(uint8x16_t) -> uint8x16_t,
-{
-    uint8x16x4_t(f(v.0), f(v.1), f(v.2), f(v.3))
+fn actg_mask(v: uint8x16_t) -> uint8x16_t {
+    unsafe {
+        let lookup = vqtbl1q_u8(LUT_ACTG, vandq_u8(v, TWO_BITS));
+        vceqq_u8(lookup, vandq_u8(v, UPPERCASE))
+    }
 }
 
+/// Membership mask for the RNA alphabet: ACTG plus `U` of either case,
+/// which shares T's 2-bit code in the packed formats.
 #[inline(always)]
-fn map_two_8x16x4<F>(v1: uint8x16x4_t, v2: uint8x16x4_t, mut f: F) -> uint8x16x4_t
+fn rna_mask(v: uint8x16_t) -> uint8x16_t {
+    unsafe {
+        let uppercase = vandq_u8(v, UPPERCASE);
+        vorrq_u8(actg_mask(v), vceqq_u8(uppercase, LETTER_U))
+    }
+}
+
+/// Membership mask for the protein alphabet: the letters `A..=Z` of either
+/// case plus the `*` stop codon.
+#[inline(always)]
+fn protein_mask(v: uint8x16_t) -> uint8x16_t {
+    unsafe {
+        let uppercase = vandq_u8(v, UPPERCASE);
+        let letters = vandq_u8(vcgeq_u8(uppercase, LETTER_A), vcleq_u8(uppercase, LETTER_Z));
+        vorrq_u8(letters, vceqq_u8(v, STAR))
+    }
+}
+
+#[inline(always)]
+fn map_8x16x4<F>(v: uint8x16x4_t, mut f: F) -> uint8x16x4_t
 where
-    F: FnMut(uint8x16_t, uint8x16_t) -> uint8x16_t,
+    F: FnMut(uint8x16_t) -> uint8x16_t,
 {
-    uint8x16x4_t(f(v1.0, v2.0), f(v1.1, v2.1), f(v1.2, v2.2), f(v1.3, v2.3))
+    uint8x16x4_t(f(v.0), f(v.1), f(v.2), f(v.3))
 }
 
 // computing movemask is significantly more expensive than on x86
@@ -146,6 +212,33 @@ fn movemask_64(v: uint8x16x4_t) -> u64 {
     }
 }
 
+/// Compute `(min, max, sum)` over an arbitrary-length byte slice in 16-byte
+/// strides, e.g. to summarize a quality line.
+/// `min` is `u8::MAX` over an empty slice.
+#[inline(always)]
+pub fn byte_stats(buf: &[u8]) -> (u8, u8, u64) {
+    unsafe {
+        let mut v_min = vdupq_n_u8(u8::MAX);
+        let mut v_max = vdupq_n_u8(0);
+        let mut sum = 0u64;
+        let mut chunks = buf.chunks_exact(16);
+        for chunk in &mut chunks {
+            let v = vld1q_u8(chunk.as_ptr());
+            v_min = vminq_u8(v_min, v);
+            v_max = vmaxq_u8(v_max, v);
+            sum += vaddlvq_u8(v) as u64;
+        }
+        let mut min = vminvq_u8(v_min);
+        let mut max = vmaxvq_u8(v_max);
+        for &x in chunks.remainder() {
+            min = min.min(x);
+            max = max.max(x);
+            sum += x as u64;
+        }
+        (min, max, sum)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,7 +257,8 @@ mod tests {
         unsafe {
             let buf = *b">CGT>CGT>CG>CG>CGTACGTACGT>CGT>CGT>CGT>CGT>CGT>CGTACGTACGT>CGT>A";
             let v = vld4q_u8(buf.as_ptr());
-            let mask = map_8x16x4(v, |v| vceqq_u8(v, GREATER_THAN));
+            let greater_than = vdupq_n_u8(b'>');
+            let mask = map_8x16x4(v, |v| vceqq_u8(v, greater_than));
             let res = movemask_64(mask);
             assert_eq!(
                 0b0100010000000000010001000100010001000100000000000100100100010001,
@@ -0,0 +1,258 @@
+#![allow(clippy::missing_transmute_annotations)]
+
+use crate::config::{advanced::*, *};
+use crate::lexer::*;
+use core::arch::wasm32::*;
+use core::mem::transmute;
+
+const SEMICOLON: v128 = unsafe { transmute([b';'; 16]) };
+const LINE_FEED: v128 = unsafe { transmute([b'\n'; 16]) };
+const DASH: v128 = unsafe { transmute([b'-'; 16]) };
+const DOT: v128 = unsafe { transmute([b'.'; 16]) };
+const UPPERCASE: v128 = unsafe { transmute([0b11011111u8; 16]) };
+const TWO_BITS: v128 = unsafe { transmute([0b110u8; 16]) };
+const LUT_ACTG: v128 = unsafe { transmute(*b"A_C_T_G_________") };
+const LETTER_U: v128 = unsafe { transmute([b'U'; 16]) };
+const STAR: v128 = unsafe { transmute([b'*'; 16]) };
+const LETTER_A: v128 = unsafe { transmute([b'A'; 16]) };
+const LETTER_Z: v128 = unsafe { transmute([b'Z'; 16]) };
+
+/// Four consecutive 16-byte lanes covering a 64-byte chunk.
+/// Unlike NEON's `vld4q_u8`, the lanes are not interleaved.
+type U8x16x4 = (v128, v128, v128, v128);
+
+#[inline(always)]
+pub fn extract_fasta_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastaBitmask {
+    unsafe {
+        let ptr = buf.as_ptr() as *const v128;
+        let v = (
+            v128_load(ptr),
+            v128_load(ptr.add(1)),
+            v128_load(ptr.add(2)),
+            v128_load(ptr.add(3)),
+        );
+
+        // the separator is configurable, so the compared vector cannot be const
+        let header = u8x16_splat(header_byte(CONFIG));
+        let open_bracket = movemask_64(map_8x16x4(v, |v| u8x16_eq(v, header)));
+        let line_feeds = movemask_64(map_8x16x4(v, |v| u8x16_eq(v, LINE_FEED)));
+        let semicolons = if flag_is_set(CONFIG, FASTA_COMMENTS) {
+            movemask_64(map_8x16x4(v, |v| u8x16_eq(v, SEMICOLON)))
+        } else {
+            0
+        };
+
+        let mut is_dna = !0;
+        let mut gaps = 0;
+        let mut two_bits = 0;
+        let mut high_bit = 0;
+        let mut low_bit = 0;
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
+            high_bit = movemask_64(map_8x16x4(v, |v| u8x16_shl(v, 5)));
+            low_bit = movemask_64(map_8x16x4(v, |v| u8x16_shl(v, 6)));
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
+            // the 2-bit interleave does not map cleanly onto v128 ops
+            for (i, &x) in buf.iter().enumerate().take(64) {
+                two_bits |= ((x & 0b110) as u128) << (2 * i).wrapping_sub(1);
+            }
+        }
+
+        if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(map_8x16x4(v, protein_mask))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(map_8x16x4(v, rna_mask))
+            } else {
+                movemask_64(map_8x16x4(v, actg_mask))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = movemask_64(map_8x16x4(v, |v| {
+                v128_or(u8x16_eq(v, DASH), u8x16_eq(v, DOT))
+            }));
+        }
+
+        FastaBitmask {
+            open_bracket,
+            semicolons,
+            line_feeds,
+            is_dna,
+            gaps,
+            two_bits,
+            high_bit,
+            low_bit,
+        }
+    }
+}
+
+#[inline(always)]
+pub fn extract_fastq_bitmask<const CONFIG: Config>(buf: &[u8]) -> FastqBitmask {
+    unsafe {
+        let ptr = buf.as_ptr() as *const v128;
+        let v = (
+            v128_load(ptr),
+            v128_load(ptr.add(1)),
+            v128_load(ptr.add(2)),
+            v128_load(ptr.add(3)),
+        );
+
+        let line_feeds = movemask_64(map_8x16x4(v, |v| u8x16_eq(v, LINE_FEED)));
+
+        let mut is_dna = !0;
+        let mut gaps = 0;
+        let mut two_bits = 0;
+        let mut high_bit = 0;
+        let mut low_bit = 0;
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_COLUMNAR | COMPUTE_BASE_COUNTS) {
+            high_bit = movemask_64(map_8x16x4(v, |v| u8x16_shl(v, 5)));
+            low_bit = movemask_64(map_8x16x4(v, |v| u8x16_shl(v, 6)));
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_DNA_PACKED | KMER_MASK) {
+            // the 2-bit interleave does not map cleanly onto v128 ops
+            for (i, &x) in buf.iter().enumerate().take(64) {
+                two_bits |= ((x & 0b110) as u128) << (2 * i).wrapping_sub(1);
+            }
+        }
+
+        if flag_is_set(CONFIG, SPLIT_NON_ACTG) {
+            is_dna = if flag_is_set(CONFIG, ALPHABET_PROTEIN) {
+                movemask_64(map_8x16x4(v, protein_mask))
+            } else if flag_is_set(CONFIG, ALPHABET_RNA) {
+                movemask_64(map_8x16x4(v, rna_mask))
+            } else {
+                movemask_64(map_8x16x4(v, actg_mask))
+            };
+        }
+
+        if flag_is_set(CONFIG, COMPUTE_GAP_MASK) {
+            gaps = movemask_64(map_8x16x4(v, |v| {
+                v128_or(u8x16_eq(v, DASH), u8x16_eq(v, DOT))
+            }));
+        }
+
+        FastqBitmask {
+            line_feeds,
+            is_dna,
+            gaps,
+            two_bits,
+            high_bit,
+            low_bit,
+        }
+    }
+}
+
+/// Membership mask for the ACTG alphabet of either case.
+#[inline(always)]
+fn actg_mask(v: v128) -> v128 {
+    let lookup = u8x16_swizzle(LUT_ACTG, v128_and(v, TWO_BITS));
+    u8x16_eq(lookup, v128_and(v, UPPERCASE))
+}
+
+/// Membership mask for the RNA alphabet: ACTG plus `U` of either case,
+/// which shares T's 2-bit code in the packed formats.
+#[inline(always)]
+fn rna_mask(v: v128) -> v128 {
+    let uppercase = v128_and(v, UPPERCASE);
+    v128_or(actg_mask(v), u8x16_eq(uppercase, LETTER_U))
+}
+
+/// Membership mask for the protein alphabet: the letters `A..=Z` of either
+/// case plus the `*` stop codon.
+#[inline(always)]
+fn protein_mask(v: v128) -> v128 {
+    let uppercase = v128_and(v, UPPERCASE);
+    let letters = v128_and(u8x16_ge(uppercase, LETTER_A), u8x16_le(uppercase, LETTER_Z));
+    v128_or(letters, u8x16_eq(v, STAR))
+}
+
+#[inline(always)]
+fn map_8x16x4<F>(v: U8x16x4, mut f: F) -> U8x16x4
+where
+    F: FnMut(v128) -> v128,
+{
+    (f(v.0), f(v.1), f(v.2), f(v.3))
+}
+
+#[inline(always)]
+fn movemask_64(v: U8x16x4) -> u64 {
+    u8x16_bitmask(v.0) as u64
+        | (u8x16_bitmask(v.1) as u64) << 16
+        | (u8x16_bitmask(v.2) as u64) << 32
+        | (u8x16_bitmask(v.3) as u64) << 48
+}
+
+/// Compute `(min, max, sum)` over an arbitrary-length byte slice in 16-byte
+/// strides, e.g. to summarize a quality line.
+/// `min` is `u8::MAX` over an empty slice.
+#[inline(always)]
+pub fn byte_stats(buf: &[u8]) -> (u8, u8, u64) {
+    let mut v_min = u8x16_splat(u8::MAX);
+    let mut v_max = u8x16_splat(0);
+    let mut sum = 0u64;
+    let mut chunks = buf.chunks_exact(16);
+    for chunk in &mut chunks {
+        let v = unsafe { v128_load(chunk.as_ptr() as *const v128) };
+        v_min = u8x16_min(v_min, v);
+        v_max = u8x16_max(v_max, v);
+        let pairs = u32x4_extadd_pairwise_u16x8(u16x8_extadd_pairwise_u8x16(v));
+        let lanes: [u32; 4] = unsafe { transmute(pairs) };
+        sum += lanes.into_iter().map(u64::from).sum::<u64>();
+    }
+    let mins: [u8; 16] = unsafe { transmute(v_min) };
+    let maxs: [u8; 16] = unsafe { transmute(v_max) };
+    let mut min = mins.into_iter().min().unwrap();
+    let mut max = maxs.into_iter().max().unwrap();
+    for &x in chunks.remainder() {
+        min = min.min(x);
+        max = max.max(x);
+        sum += x as u64;
+    }
+    (min, max, sum)
+}
+
+// the scalar reference implementation, compiled alongside for cross-checking
+#[cfg(test)]
+#[path = "fallback.rs"]
+mod fallback;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: Config = SPLIT_NON_ACTG
+        | COMPUTE_DNA_COLUMNAR
+        | COMPUTE_DNA_PACKED
+        | COMPUTE_GAP_MASK
+        | FASTA_COMMENTS;
+
+    #[test]
+    fn test_matches_fallback() {
+        let buf: [u8; 64] = core::array::from_fn(|i| b">;\nACGT-acgt.Nn\n+@I~!"[i % 21]);
+
+        let simd = extract_fasta_bitmask::<CONFIG>(&buf);
+        let scalar = fallback::extract_fasta_bitmask::<CONFIG>(&buf);
+        assert_eq!(simd.open_bracket, scalar.open_bracket);
+        assert_eq!(simd.semicolons, scalar.semicolons);
+        assert_eq!(simd.line_feeds, scalar.line_feeds);
+        assert_eq!(simd.is_dna, scalar.is_dna);
+        assert_eq!(simd.gaps, scalar.gaps);
+        assert_eq!(simd.two_bits, scalar.two_bits);
+        assert_eq!(simd.high_bit, scalar.high_bit);
+        assert_eq!(simd.low_bit, scalar.low_bit);
+
+        let simd = extract_fastq_bitmask::<CONFIG>(&buf);
+        let scalar = fallback::extract_fastq_bitmask::<CONFIG>(&buf);
+        assert_eq!(simd.line_feeds, scalar.line_feeds);
+        assert_eq!(simd.is_dna, scalar.is_dna);
+        assert_eq!(simd.gaps, scalar.gaps);
+        assert_eq!(simd.two_bits, scalar.two_bits);
+        assert_eq!(simd.high_bit, scalar.high_bit);
+        assert_eq!(simd.low_bit, scalar.low_bit);
+    }
+}
@@ -0,0 +1,211 @@
+//! Async record streaming (requires the `tokio` feature).
+
+use crate::config::{advanced::*, *};
+use crate::input::*;
+use crate::parser::*;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::io;
+use tokio::io::{AsyncRead, ReadBuf};
+
+const READ_CHUNK_SIZE: usize = 1 << 16;
+
+/// Buffered input backed by an [`AsyncRead`].
+/// It mirrors the buffering of [`ReaderInput`] but awaits on refill, so it
+/// never blocks a thread while waiting for data.
+pub struct AsyncReaderInput<R: AsyncRead + Unpin + Send> {
+    reader: R,
+    scratch: Box<[u8]>,
+    buf: Vec<u8>,
+    start: bool,
+    eof: bool,
+}
+
+impl<R: AsyncRead + Unpin + Send> AsyncReaderInput<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            scratch: vec![0; READ_CHUNK_SIZE].into_boxed_slice(),
+            buf: Vec::new(),
+            start: true,
+            eof: false,
+        }
+    }
+
+    /// Poll one read into the internal buffer.
+    /// Returns the number of appended bytes, 0 at the end of the input.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut read_buf = ReadBuf::new(&mut self.scratch);
+        match Pin::new(&mut self.reader).poll_read(cx, &mut read_buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) => {
+                let mut filled = read_buf.filled();
+                if filled.is_empty() {
+                    self.eof = true;
+                } else if self.start {
+                    // skip a leading UTF-8 BOM so that offsets are relative to the actual content
+                    if filled.starts_with(&UTF8_BOM) {
+                        filled = &filled[UTF8_BOM.len()..];
+                    }
+                    self.start = false;
+                }
+                self.buf.extend_from_slice(filled);
+                Poll::Ready(Ok(filled.len()))
+            }
+        }
+    }
+}
+
+/// A [`Stream`] of [`OwnedRecord`], parsing FASTQ from an async reader.
+/// Data is buffered until a record boundary and parsed in batches with the
+/// regular [`FastqParser`].
+pub struct FastqRecordStream<const CONFIG: Config, R: AsyncRead + Unpin + Send> {
+    input: AsyncReaderInput<R>,
+    records: VecDeque<OwnedRecord>,
+    scanned: usize,
+    newlines: usize,
+    boundary: usize,
+}
+
+impl<const CONFIG: Config, R: AsyncRead + Unpin + Send> FastqRecordStream<CONFIG, R> {
+    pub fn new(input: AsyncReaderInput<R>) -> Self {
+        assert!(flag_is_set(CONFIG, RETURN_RECORD));
+        Self {
+            input,
+            records: VecDeque::new(),
+            scanned: 0,
+            newlines: 0,
+            boundary: 0,
+        }
+    }
+
+    /// Scan the newly buffered bytes for the last complete record boundary,
+    /// i.e. the position after every fourth line feed.
+    fn scan_boundary(&mut self) {
+        for (i, &x) in self.input.buf.iter().enumerate().skip(self.scanned) {
+            if x == b'\n' {
+                self.newlines += 1;
+                if self.newlines.is_multiple_of(4) {
+                    self.boundary = i + 1;
+                }
+            }
+        }
+        self.scanned = self.input.buf.len();
+    }
+
+    /// Parse the buffered bytes up to `end` and queue the resulting records.
+    fn parse_batch(&mut self, end: usize) {
+        if end == 0 {
+            return;
+        }
+        let mut parser = FastqParser::<CONFIG, _>::from_slice(&self.input.buf[..end]);
+        while let Some(event) = parser.next() {
+            if let Event::Record(_) = event {
+                self.records.push_back(OwnedRecord {
+                    header: if flag_is_set(CONFIG, COMPUTE_HEADER) {
+                        parser.get_header_owned()
+                    } else {
+                        Vec::new()
+                    },
+                    seq: if flag_is_set(CONFIG, COMPUTE_DNA_STRING) {
+                        parser.get_dna_string_owned()
+                    } else {
+                        Vec::new()
+                    },
+                    qual: if flag_is_set(CONFIG, COMPUTE_QUALITY) {
+                        parser.get_quality_owned()
+                    } else {
+                        None
+                    },
+                });
+            }
+        }
+        self.input.buf.drain(..end);
+        self.scanned -= end;
+        // newlines already scanned past the boundary keep contributing
+        self.newlines %= 4;
+        self.boundary = 0;
+    }
+}
+
+impl<const CONFIG: Config, R: AsyncRead + Unpin + Send> Stream for FastqRecordStream<CONFIG, R> {
+    type Item = OwnedRecord;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<OwnedRecord>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(record) = this.records.pop_front() {
+                return Poll::Ready(Some(record));
+            }
+            if this.input.eof {
+                return Poll::Ready(None);
+            }
+            match this.input.poll_fill(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => panic!("Error while reading data: {e}"),
+                Poll::Ready(Ok(0)) => {
+                    // parse whatever remains, including a trailing partial record
+                    let end = this.input.buf.len();
+                    this.parse_batch(end);
+                }
+                Poll::Ready(Ok(_)) => {
+                    this.scan_boundary();
+                    if this.boundary > 0 {
+                        let end = this.boundary;
+                        this.parse_batch(end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, const CONFIG: Config> FastqParser<'a, CONFIG, SliceInput<'a>> {
+    /// Parse records from an async reader, yielding them as a [`Stream`] of
+    /// [`OwnedRecord`]. This requires the [`RETURN_RECORD`] flag.
+    /// Only the fields computed by the configuration are populated.
+    pub fn from_async_reader<R: AsyncRead + Unpin + Send>(
+        reader: R,
+    ) -> FastqRecordStream<CONFIG, R> {
+        FastqRecordStream::new(AsyncReaderInput::new(reader))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use tokio::io::AsyncWriteExt;
+
+    const CONFIG: Config = ParserOptions::default().compute_quality().config();
+
+    #[tokio::test]
+    async fn test_fastq_record_stream() {
+        // a small duplex buffer forces the stream to await between batches
+        let (mut tx, rx) = tokio::io::duplex(16);
+        let writer = tokio::spawn(async move {
+            for i in 0..10 {
+                tx.write_all(format!("@r{i}\nACGTACGTAC\n+\nIIIIIIIIII\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut stream = FastqParser::<CONFIG, _>::from_async_reader(rx);
+        let mut records = Vec::new();
+        while let Some(record) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            records.push(record);
+        }
+        writer.await.unwrap();
+
+        assert_eq!(records.len(), 10);
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record.header, format!("r{i}").into_bytes());
+            assert_eq!(record.seq, b"ACGTACGTAC");
+            assert_eq!(record.qual.as_deref(), Some(b"IIIIIIIIII".as_slice()));
+        }
+    }
+}